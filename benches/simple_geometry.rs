@@ -1,15 +1,15 @@
 use std::num::NonZeroU16;
 
-use bevy::prelude::{Transform, UVec2, Vec3};
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use bevy::prelude::{Rect, Transform, UVec2, Vec2, Vec3};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 use oxidized_navigation::{
     build_tile_sync,
-    conversion::{ColliderType, GeometryCollection, GeometryToConvert},
-    NavMeshSettings,
+    conversion::{ColliderType, GeometryCollection, GeometryToConvert, Tessellation},
+    NavMeshSettings, RegionPartitioning, SpanFilters,
 };
 use parry3d::shape::Cuboid;
 
-fn generate_single_primitive_geometry() {
+fn generate_single_primitive_geometry(partitioning: RegionPartitioning) {
     let tile_coord = UVec2::new(0, 0);
     let heightfields = Box::default();
 
@@ -19,12 +19,13 @@ fn generate_single_primitive_geometry() {
             Vec3::new(10.0, 0.2, 10.0).into(),
         ))),
         area: None,
+        tessellation: None,
     }];
     let nav_mesh_settings = NavMeshSettings {
         cell_width: 0.25,
         cell_height: 0.1,
         tile_width: 100,
-        world_half_extents: 12.5,
+        world_bounds: Rect::from_center_half_size(Vec2::ZERO, Vec2::splat(12.5)),
         world_bottom_bound: -100.0,
         max_traversable_slope_radians: (40.0_f32 - 0.1).to_radians(),
         walkable_height: 20,
@@ -35,6 +36,16 @@ fn generate_single_primitive_geometry() {
         max_contour_simplification_error: 1.1,
         max_edge_length: 80,
         max_tile_generation_tasks: NonZeroU16::new(1),
+        max_rasterization_tasks: None,
+        cache_open_tiles: false,
+        jump_links: None,
+        partitioning,
+        span_filters: SpanFilters::default(),
+        median_area_filter: false,
+        tessellation: Tessellation::default(),
+        streaming: None,
+        affector_debounce: None,
+        deterministic: false,
     };
 
     black_box(build_tile_sync(
@@ -45,7 +56,7 @@ fn generate_single_primitive_geometry() {
     ));
 }
 
-fn generate_many_primitive_geometry() {
+fn generate_many_primitive_geometry(partitioning: RegionPartitioning) {
     let tile_coord = UVec2::new(0, 0);
     let heightfields = Box::default();
 
@@ -56,6 +67,7 @@ fn generate_many_primitive_geometry() {
                 Vec3::new(10.0, 0.2, 10.0).into(),
             ))),
             area: None,
+            tessellation: None,
         },
         GeometryCollection {
             transform: Transform::from_xyz(5.0, 1.0, 0.0),
@@ -63,6 +75,7 @@ fn generate_many_primitive_geometry() {
                 Vec3::new(1.0, 1.0, 1.0).into(),
             ))),
             area: None,
+            tessellation: None,
         },
         GeometryCollection {
             transform: Transform::from_xyz(-5.0, 1.0, 2.0),
@@ -70,6 +83,7 @@ fn generate_many_primitive_geometry() {
                 Vec3::new(4.0, 1.0, 1.0).into(),
             ))),
             area: None,
+            tessellation: None,
         },
         GeometryCollection {
             transform: Transform::from_xyz(-2.5, 2.0, 2.0),
@@ -77,6 +91,7 @@ fn generate_many_primitive_geometry() {
                 Vec3::new(1.0, 2.0, 1.0).into(),
             ))),
             area: None,
+            tessellation: None,
         },
         GeometryCollection {
             transform: Transform::from_xyz(-2.5, 2.0, -2.0),
@@ -84,13 +99,14 @@ fn generate_many_primitive_geometry() {
                 Vec3::new(1.0, 2.0, 1.0).into(),
             ))),
             area: None,
+            tessellation: None,
         },
     ];
     let nav_mesh_settings = NavMeshSettings {
         cell_width: 0.25,
         cell_height: 0.1,
         tile_width: 100,
-        world_half_extents: 12.5,
+        world_bounds: Rect::from_center_half_size(Vec2::ZERO, Vec2::splat(12.5)),
         world_bottom_bound: -100.0,
         max_traversable_slope_radians: (40.0_f32 - 0.1).to_radians(),
         walkable_height: 20,
@@ -101,6 +117,16 @@ fn generate_many_primitive_geometry() {
         max_contour_simplification_error: 1.1,
         max_edge_length: 80,
         max_tile_generation_tasks: NonZeroU16::new(1),
+        max_rasterization_tasks: None,
+        cache_open_tiles: false,
+        jump_links: None,
+        partitioning,
+        span_filters: SpanFilters::default(),
+        median_area_filter: false,
+        tessellation: Tessellation::default(),
+        streaming: None,
+        affector_debounce: None,
+        deterministic: false,
     };
 
     black_box(build_tile_sync(
@@ -111,13 +137,32 @@ fn generate_many_primitive_geometry() {
     ));
 }
 
+const PARTITIONINGS: [RegionPartitioning; 3] = [
+    RegionPartitioning::Watershed,
+    RegionPartitioning::Monotone,
+    RegionPartitioning::Layers,
+];
+
 fn criterion_benchmark(c: &mut Criterion) {
-    c.bench_function("Generate Single Primitive Geometry", |b| {
-        b.iter(generate_single_primitive_geometry)
-    });
-    c.bench_function("Generate Many Primitive Geometry", |b| {
-        b.iter(generate_many_primitive_geometry)
-    });
+    let mut group = c.benchmark_group("Generate Single Primitive Geometry");
+    for partitioning in PARTITIONINGS {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{partitioning:?}")),
+            &partitioning,
+            |b, partitioning| b.iter(|| generate_single_primitive_geometry(*partitioning)),
+        );
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("Generate Many Primitive Geometry");
+    for partitioning in PARTITIONINGS {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{partitioning:?}")),
+            &partitioning,
+            |b, partitioning| b.iter(|| generate_many_primitive_geometry(*partitioning)),
+        );
+    }
+    group.finish();
 }
 
 criterion_group!(benches, criterion_benchmark);
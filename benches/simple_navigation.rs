@@ -1,16 +1,16 @@
 use std::num::NonZeroU16;
 
 use bevy::{
-    prelude::{Transform, UVec2, Vec3},
+    prelude::{Rect, Transform, UVec2, Vec2, Vec3},
     utils::HashMap,
 };
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use oxidized_navigation::{
     build_tile_sync,
-    conversion::{ColliderType, GeometryCollection, GeometryToConvert},
+    conversion::{ColliderType, GeometryCollection, GeometryToConvert, Tessellation},
     query::find_path,
     tiles::{NavMeshTile, NavMeshTiles},
-    NavMeshSettings,
+    NavMeshSettings, RegionPartitioning, SpanFilters,
 };
 use parry3d::shape::Cuboid;
 
@@ -24,6 +24,7 @@ fn generate_single_primitive_geometry(nav_mesh_settings: &NavMeshSettings) -> Na
             Vec3::new(10.0, 0.2, 10.0).into(),
         ))),
         area: None,
+        tessellation: None,
     }];
 
     build_tile_sync(
@@ -45,6 +46,7 @@ fn generate_many_primitive_geometry(nav_mesh_settings: &NavMeshSettings) -> NavM
                 Vec3::new(10.0, 0.2, 10.0).into(),
             ))),
             area: None,
+            tessellation: None,
         },
         GeometryCollection {
             transform: Transform::from_xyz(5.0, 1.0, 0.0),
@@ -52,6 +54,7 @@ fn generate_many_primitive_geometry(nav_mesh_settings: &NavMeshSettings) -> NavM
                 Vec3::new(1.0, 1.0, 1.0).into(),
             ))),
             area: None,
+            tessellation: None,
         },
         GeometryCollection {
             transform: Transform::from_xyz(-5.0, 1.0, 2.0),
@@ -59,6 +62,7 @@ fn generate_many_primitive_geometry(nav_mesh_settings: &NavMeshSettings) -> NavM
                 Vec3::new(4.0, 1.0, 1.0).into(),
             ))),
             area: None,
+            tessellation: None,
         },
         GeometryCollection {
             transform: Transform::from_xyz(-2.5, 2.0, 2.0),
@@ -66,6 +70,7 @@ fn generate_many_primitive_geometry(nav_mesh_settings: &NavMeshSettings) -> NavM
                 Vec3::new(1.0, 2.0, 1.0).into(),
             ))),
             area: None,
+            tessellation: None,
         },
         GeometryCollection {
             transform: Transform::from_xyz(-2.5, 2.0, -2.0),
@@ -73,6 +78,7 @@ fn generate_many_primitive_geometry(nav_mesh_settings: &NavMeshSettings) -> NavM
                 Vec3::new(1.0, 2.0, 1.0).into(),
             ))),
             area: None,
+            tessellation: None,
         },
     ];
 
@@ -89,7 +95,7 @@ fn criterion_benchmark(c: &mut Criterion) {
         cell_width: 0.25,
         cell_height: 0.1,
         tile_width: 100,
-        world_half_extents: 12.5,
+        world_bounds: Rect::from_center_half_size(Vec2::ZERO, Vec2::splat(12.5)),
         world_bottom_bound: -100.0,
         max_traversable_slope_radians: (40.0_f32 - 0.1).to_radians(),
         walkable_height: 20,
@@ -100,6 +106,16 @@ fn criterion_benchmark(c: &mut Criterion) {
         max_contour_simplification_error: 1.1,
         max_edge_length: 80,
         max_tile_generation_tasks: NonZeroU16::new(1),
+        max_rasterization_tasks: None,
+        cache_open_tiles: false,
+        jump_links: None,
+        partitioning: RegionPartitioning::Watershed,
+        span_filters: SpanFilters::default(),
+        median_area_filter: false,
+        tessellation: Tessellation::default(),
+        streaming: None,
+        affector_debounce: None,
+        deterministic: false,
     };
 
     let simple_tiles = NavMeshTiles {
@@ -130,6 +146,7 @@ fn criterion_benchmark(c: &mut Criterion) {
                 Vec3::new(0.0, 0.0, 0.0),
                 None,
                 None,
+                None,
             ))
         })
     });
@@ -142,6 +159,7 @@ fn criterion_benchmark(c: &mut Criterion) {
                 Vec3::new(0.0, 0.0, 0.0),
                 None,
                 None,
+                None,
             ))
         })
     });
@@ -83,6 +83,7 @@ fn run_blocking_pathfinding(
             end_pos,
             None,
             Some(&[1.0, 0.5]),
+            None,
         ) {
             Ok(path) => {
                 info!("Path found (BLOCKING): {:?}", path);
@@ -191,6 +192,7 @@ async fn async_path_find(
         end_pos,
         position_search_radius,
         Some(&[1.0, 0.5]),
+        None,
     ) {
         Ok(path) => {
             info!("Found path (ASYNC): {:?}", path);
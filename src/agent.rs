@@ -0,0 +1,451 @@
+//! Module for agents that find & follow paths on their own.
+//!
+//! Give an entity a [NavAgent] with a destination & the plugin will request a path in the background, advance along its corners & emit [NavAgentArrived] or [NavAgentPathFailed].
+//!
+//! Agents are moved by changing their ``Transform`` directly, so they should not have a parent. Use [NavAgentMovement::DesiredVelocity] to move them yourself, for example with a character controller.
+use std::sync::{Arc, RwLock};
+
+use bevy::{
+    prelude::{
+        error, App, Commands, Component, Entity, Event, EventReader, EventWriter,
+        IntoSystemConfigs, Plugin, Query, Rect, Reflect, ReflectComponent, RemovedComponents, Res,
+        Time, Transform, UVec2, Update, Vec3, Vec3Swizzles, With, Without,
+    },
+    tasks::{futures_lite::future, AsyncComputeTaskPool, Task},
+};
+
+use crate::{
+    query::{find_polygon_path, perform_string_pulling_on_path, FindPathError},
+    tiles::{NavMeshTiles, PolygonFilter},
+    NavMesh, NavMeshSettings, OutOfWorldBounds, OxidizedNavigation, TileGenerated,
+};
+
+pub struct OxidizedNavigationAgentPlugin;
+impl Plugin for OxidizedNavigationAgentPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                remove_nav_agent_path_tasks_system,
+                mark_nav_agents_for_repath_system,
+                request_nav_agent_paths_system,
+                receive_nav_agent_paths_system,
+                follow_nav_agent_paths_system,
+            )
+                .chain()
+                .after(OxidizedNavigation::Main),
+        );
+
+        app.register_type::<NavAgent>()
+            .register_type::<NavAgentMovement>()
+            .register_type::<RepathPolicy>()
+            .register_type::<NavAgentStatus>();
+
+        app.add_event::<NavAgentArrived>()
+            .add_event::<NavAgentPathFailed>();
+    }
+}
+
+/// How a [NavAgent] moves along its path.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NavAgentMovement {
+    /// Move the ``Transform`` along the path.
+    #[default]
+    Transform,
+    /// Only write [NavAgent::desired_velocity], leaving it up to you to move the agent.
+    DesiredVelocity,
+}
+
+/// When a [NavAgent] should find a new path to its destination.
+#[derive(Reflect, Clone, Copy, Debug)]
+pub struct RepathPolicy {
+    /// Find a new path when a tile the current path crosses has been regenerated.
+    pub on_tile_regenerated: bool,
+    /// Find a new path at this interval, in seconds.
+    pub interval: Option<f32>,
+    /// Try again after this many seconds if finding a path failed. If **``None``** the agent waits until its destination changes.
+    pub retry_delay: Option<f32>,
+}
+impl Default for RepathPolicy {
+    fn default() -> Self {
+        Self {
+            on_tile_regenerated: true,
+            interval: None,
+            retry_delay: Some(1.0),
+        }
+    }
+}
+
+/// Current state of a [NavAgent].
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NavAgentStatus {
+    /// The agent has no destination.
+    #[default]
+    Idle,
+    /// Waiting for a path to be found.
+    FindingPath,
+    /// Moving along its path.
+    Moving,
+    /// Within [NavAgent::arrival_tolerance] of the end of its path, the point on the nav-mesh closest to its destination.
+    Arrived,
+    /// No path could be found to the destination.
+    Failed,
+}
+
+/// Component for entities that should find & follow paths to [NavAgent::destination].
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct NavAgent {
+    /// Position the agent should move to. Changing this finds a new path.
+    pub destination: Option<Vec3>,
+    /// Movement speed, in world units per second.
+    pub speed: f32,
+    /// How close the agent has to get to the end of its path to have arrived, in world units. The end of the path is the destination clamped onto the nav-mesh.
+    pub arrival_tolerance: f32,
+    pub repath_policy: RepathPolicy,
+    pub movement: NavAgentMovement,
+    /// Velocity towards the next corner of the path. Written by the agent systems.
+    pub desired_velocity: Vec3,
+    /// Radius to search for a start & end polygon in. See [crate::query::find_path].
+    pub position_search_radius: Option<f32>,
+    /// Filter for which polygons the agent may cross. See [crate::query::find_path].
+    #[reflect(ignore)]
+    pub polygon_filter: Option<PolygonFilter>,
+    status: NavAgentStatus,
+    /// Remaining corners of the path.
+    #[reflect(ignore)]
+    path: Vec<Vec3>,
+    /// Tiles crossed by the path.
+    #[reflect(ignore)]
+    path_tiles: Vec<UVec2>,
+    /// The destination the current path was found for.
+    #[reflect(ignore)]
+    path_destination: Option<Vec3>,
+    /// Time until the next repath, in seconds.
+    #[reflect(ignore)]
+    repath_timer: Option<f32>,
+}
+impl Default for NavAgent {
+    fn default() -> Self {
+        Self::new(3.5)
+    }
+}
+impl NavAgent {
+    pub fn new(speed: f32) -> Self {
+        Self {
+            destination: None,
+            speed,
+            arrival_tolerance: 0.25,
+            repath_policy: RepathPolicy::default(),
+            movement: NavAgentMovement::default(),
+            desired_velocity: Vec3::ZERO,
+            position_search_radius: None,
+            polygon_filter: None,
+            status: NavAgentStatus::default(),
+            path: Vec::new(),
+            path_tiles: Vec::new(),
+            path_destination: None,
+            repath_timer: None,
+        }
+    }
+
+    /// Setter for [`NavAgent::destination`]
+    pub fn with_destination(mut self, destination: Vec3) -> Self {
+        self.destination = Some(destination);
+
+        self
+    }
+    /// Setter for [`NavAgent::arrival_tolerance`]
+    pub fn with_arrival_tolerance(mut self, arrival_tolerance: f32) -> Self {
+        self.arrival_tolerance = arrival_tolerance;
+
+        self
+    }
+    /// Setter for [`NavAgent::repath_policy`]
+    pub fn with_repath_policy(mut self, repath_policy: RepathPolicy) -> Self {
+        self.repath_policy = repath_policy;
+
+        self
+    }
+    /// Setter for [`NavAgent::movement`]
+    pub fn with_movement(mut self, movement: NavAgentMovement) -> Self {
+        self.movement = movement;
+
+        self
+    }
+    /// Setter for [`NavAgent::polygon_filter`]
+    pub fn with_polygon_filter(mut self, polygon_filter: PolygonFilter) -> Self {
+        self.polygon_filter = Some(polygon_filter);
+
+        self
+    }
+
+    pub fn status(&self) -> NavAgentStatus {
+        self.status
+    }
+
+    /// Returns the remaining corners of the agent's path.
+    pub fn path(&self) -> &[Vec3] {
+        &self.path
+    }
+
+    /// Finds a new path to the destination next update.
+    pub fn repath(&mut self) {
+        self.repath_timer = Some(0.0);
+    }
+}
+
+/// Event sent when a [NavAgent] has arrived at its destination.
+#[derive(Event, Debug)]
+pub struct NavAgentArrived(pub Entity);
+
+/// Event sent when no path could be found for a [NavAgent].
+#[derive(Event, Debug)]
+pub struct NavAgentPathFailed {
+    pub entity: Entity,
+    pub error: FindPathError,
+}
+
+type PathResult = Result<(Vec<Vec3>, Vec<UVec2>), FindPathError>;
+
+#[derive(Component)]
+struct NavAgentPathTask {
+    /// ``None`` if the nav-mesh lock has been poisoned.
+    task: Task<Option<PathResult>>,
+    destination: Vec3,
+}
+
+/// Drops the path tasks of entities that are no longer agents, cancelling them.
+fn remove_nav_agent_path_tasks_system(
+    mut commands: Commands,
+    mut removed_agents: RemovedComponents<NavAgent>,
+    query: Query<Entity, With<NavAgentPathTask>>,
+) {
+    for entity in removed_agents.read() {
+        if query.contains(entity) {
+            commands.entity(entity).remove::<NavAgentPathTask>();
+        }
+    }
+}
+
+fn mark_nav_agents_for_repath_system(
+    mut tile_generated: EventReader<TileGenerated>,
+    mut query: Query<&mut NavAgent>,
+) {
+    let generated_tiles: Vec<UVec2> = tile_generated.read().map(|event| event.0).collect();
+    if generated_tiles.is_empty() {
+        return;
+    }
+
+    for mut agent in query.iter_mut().filter(|agent| {
+        agent.repath_policy.on_tile_regenerated
+            && agent.status == NavAgentStatus::Moving
+            && agent
+                .path_tiles
+                .iter()
+                .any(|tile| generated_tiles.contains(tile))
+    }) {
+        agent.repath();
+    }
+}
+
+fn request_nav_agent_paths_system(
+    mut commands: Commands,
+    nav_mesh_settings: Res<NavMeshSettings>,
+    nav_mesh: Res<NavMesh>,
+    time: Res<Time>,
+    mut out_of_world_bounds: EventWriter<OutOfWorldBounds>,
+    mut query: Query<(Entity, &mut NavAgent, &Transform), Without<NavAgentPathTask>>,
+) {
+    let thread_pool = AsyncComputeTaskPool::get();
+
+    for (entity, mut agent, transform) in query.iter_mut() {
+        let Some(destination) = agent.destination else {
+            if agent.status != NavAgentStatus::Idle {
+                agent.status = NavAgentStatus::Idle;
+                agent.path.clear();
+                agent.path_tiles.clear();
+                agent.path_destination = None;
+                agent.desired_velocity = Vec3::ZERO;
+            }
+            continue;
+        };
+
+        let repath_due = if let Some(repath_timer) = agent.repath_timer.as_mut() {
+            *repath_timer -= time.delta_seconds();
+
+            *repath_timer <= 0.0
+        } else {
+            false
+        };
+        if agent.path_destination == Some(destination) && !repath_due {
+            continue;
+        }
+
+        agent.path_destination = Some(destination);
+        agent.repath_timer = None;
+        if agent.status != NavAgentStatus::Moving {
+            // Keep following the old path while a new one is found.
+            agent.status = NavAgentStatus::FindingPath;
+        }
+
+        for position in [transform.translation.xz(), destination.xz()] {
+            if !nav_mesh_settings.is_within_world_bounds(position) {
+                out_of_world_bounds.send(OutOfWorldBounds {
+                    entity,
+                    bounds: Rect::from_corners(position, position),
+                });
+            }
+        }
+
+        let task = thread_pool.spawn(find_nav_agent_path(
+            nav_mesh.get(),
+            nav_mesh_settings.clone(),
+            transform.translation,
+            destination,
+            agent.position_search_radius,
+            agent.polygon_filter,
+        ));
+
+        commands
+            .entity(entity)
+            .insert(NavAgentPathTask { task, destination });
+    }
+}
+
+async fn find_nav_agent_path(
+    nav_mesh: Arc<RwLock<NavMeshTiles>>,
+    nav_mesh_settings: NavMeshSettings,
+    start_pos: Vec3,
+    end_pos: Vec3,
+    position_search_radius: Option<f32>,
+    polygon_filter: Option<PolygonFilter>,
+) -> Option<PathResult> {
+    let Ok(nav_mesh) = nav_mesh.read() else {
+        error!("Nav-Mesh lock has been poisoned. Agents can no longer find paths.");
+        return None;
+    };
+
+    let polygon_path = match find_polygon_path(
+        &nav_mesh,
+        &nav_mesh_settings,
+        start_pos,
+        end_pos,
+        position_search_radius,
+        None,
+        polygon_filter,
+    ) {
+        Ok(polygon_path) => polygon_path,
+        Err(error) => return Some(Err(FindPathError::PolygonPath(error))),
+    };
+
+    let path = match perform_string_pulling_on_path(&nav_mesh, start_pos, end_pos, &polygon_path) {
+        Ok(path) => path,
+        Err(error) => return Some(Err(FindPathError::StringPulling(error))),
+    };
+
+    let mut path_tiles: Vec<UVec2> = polygon_path
+        .iter()
+        .map(|poly_ref| poly_ref.tile())
+        .collect();
+    path_tiles.dedup();
+
+    Some(Ok((path, path_tiles)))
+}
+
+fn receive_nav_agent_paths_system(
+    mut commands: Commands,
+    mut path_failed: EventWriter<NavAgentPathFailed>,
+    mut query: Query<(Entity, &mut NavAgent, &mut NavAgentPathTask)>,
+) {
+    for (entity, mut agent, mut path_task) in query.iter_mut() {
+        let Some(result) = future::block_on(future::poll_once(&mut path_task.task)) else {
+            continue;
+        };
+        commands.entity(entity).remove::<NavAgentPathTask>();
+
+        if agent.destination != Some(path_task.destination) {
+            // Destination changed while the path was being found.
+            continue;
+        }
+
+        match result {
+            Some(Ok((path, path_tiles))) => {
+                agent.path = path;
+                agent.path_tiles = path_tiles;
+                agent.status = NavAgentStatus::Moving;
+                agent.repath_timer = agent.repath_policy.interval;
+            }
+            result => {
+                agent.path.clear();
+                agent.path_tiles.clear();
+                agent.desired_velocity = Vec3::ZERO;
+                agent.status = NavAgentStatus::Failed;
+                agent.repath_timer = agent.repath_policy.retry_delay;
+
+                if let Some(Err(error)) = result {
+                    path_failed.send(NavAgentPathFailed { entity, error });
+                }
+            }
+        }
+    }
+}
+
+fn follow_nav_agent_paths_system(
+    time: Res<Time>,
+    mut arrived: EventWriter<NavAgentArrived>,
+    mut query: Query<(Entity, &mut NavAgent, &mut Transform)>,
+) {
+    for (entity, mut agent, mut transform) in query.iter_mut() {
+        if agent.status != NavAgentStatus::Moving {
+            continue;
+        }
+
+        let mut position = transform.translation;
+        // Only simulate the movement if we're the ones moving the agent.
+        let mut movement_left = match agent.movement {
+            NavAgentMovement::Transform => agent.speed * time.delta_seconds(),
+            NavAgentMovement::DesiredVelocity => 0.0,
+        };
+
+        // Advance along the corners, possibly passing several in one update.
+        let mut desired_velocity = Vec3::ZERO;
+        while let Some(corner) = agent.path.first().copied() {
+            let to_corner = corner - position;
+            let distance = to_corner.length();
+
+            if agent.path.len() > 1 && distance <= movement_left.max(agent.arrival_tolerance) {
+                position += to_corner.clamp_length_max(movement_left);
+                movement_left -= distance.min(movement_left);
+                agent.path.remove(0);
+                continue;
+            }
+
+            desired_velocity = to_corner.normalize_or_zero() * agent.speed;
+            position += to_corner.clamp_length_max(movement_left);
+            break;
+        }
+
+        // The last corner is the destination clamped onto the nav-mesh, a destination inside a wall or above the floor can't be reached exactly.
+        let arrived_at_destination = match (agent.destination, agent.path.last()) {
+            (Some(_), Some(path_end)) => {
+                path_end.xz().distance(position.xz()) <= agent.arrival_tolerance
+            }
+            _ => true,
+        };
+        if arrived_at_destination {
+            agent.path.clear();
+            agent.path_tiles.clear();
+            agent.status = NavAgentStatus::Arrived;
+            agent.repath_timer = None;
+            desired_velocity = Vec3::ZERO;
+
+            arrived.send(NavAgentArrived(entity));
+        }
+
+        agent.desired_velocity = desired_velocity;
+        if agent.movement == NavAgentMovement::Transform {
+            transform.translation = position;
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! Module for generating the nav-mesh all at once, for example behind a loading screen or in tests.
+//!
+//! Call [bake_nav_mesh] with the app's [World] & it will block until every dirty tile has been generated instead of spreading the work over several frames.
+//!
+//! Tiles generated ahead of time are stored as a [BakedNavMesh], created with [BakedNavMesh::from_nav_mesh] or the ``bake_nav_mesh`` binary (``bake_cli`` feature) which bakes glTF or OBJ files without running the game. With the ``serialize`` feature it can be saved to disk. Call [load_baked_nav_mesh] to add the tiles to the nav-mesh at startup.
+use std::{thread, time::Duration};
+
+use bevy::prelude::{error, warn, Events, Schedules, UVec2, World};
+
+use crate::{
+    tiles::{Link, NavMeshTile, NavMeshTiles},
+    ActiveGenerationTasks, BakedTileCache, DebouncedTiles, DirtyObstacleTiles, DirtyTiles,
+    GenerationTicker, LoadedBakedTile, NavMesh, NavMeshSchedule, NavMeshSettings, StreamedTiles,
+    TileGenerated, MAX_BAKED_TILE_MISMATCHES,
+};
+
+/// How long to wait between checking on the generation tasks.
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Nav-mesh tiles generated ahead of time.
+#[derive(Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct BakedNavMesh {
+    /// Settings the tiles were generated with.
+    pub settings: NavMeshSettings,
+    pub tiles: Vec<BakedTile>,
+}
+impl BakedNavMesh {
+    /// Copies every tile of ``nav_mesh``, for example after generating it with [bake_nav_mesh].
+    pub fn from_nav_mesh(nav_mesh: &NavMeshTiles, nav_mesh_settings: &NavMeshSettings) -> Self {
+        let mut tiles: Vec<BakedTile> = nav_mesh
+            .get_tiles()
+            .iter()
+            .map(|(tile_coord, tile)| {
+                let mut tile = tile.clone();
+                for polygon in tile.polygons.iter_mut() {
+                    polygon
+                        .links
+                        .retain(|link| !matches!(link, Link::External { .. }));
+                }
+
+                BakedTile {
+                    tile_coord: *tile_coord,
+                    tile,
+                }
+            })
+            .collect();
+        tiles.sort_by_key(|baked_tile| (baked_tile.tile_coord.y, baked_tile.tile_coord.x));
+
+        Self {
+            settings: nav_mesh_settings.clone(),
+            tiles,
+        }
+    }
+}
+
+/// A single tile of a [BakedNavMesh].
+///
+/// ``tile`` is stored without links to neighbouring tiles, they're created when it's added to a nav-mesh.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct BakedTile {
+    pub tile_coord: UVec2,
+    pub tile: NavMeshTile,
+}
+
+/// Adds the tiles of ``baked_nav_mesh`` to the nav-mesh, replacing existing tiles with the same coordinates. Returns false if nothing was loaded.
+///
+/// Affectors are tracked as usual, but the first time the tile they're in would be generated its geometry is hashed & compared with [NavMeshTile::input_hash] instead. Only tiles whose geometry changed since they were baked are generated. A baked tile whose geometry doesn't match twice is discarded. Tiles affected by obstacles, area volumes or blockers are always generated.
+///
+/// Nothing is loaded if ``baked_nav_mesh`` was generated with [NavMeshSettings] producing different tiles than the current ones.
+pub fn load_baked_nav_mesh(world: &mut World, baked_nav_mesh: BakedNavMesh) -> bool {
+    let nav_mesh_settings = world.resource::<NavMeshSettings>().clone();
+    if !generates_same_tiles(&nav_mesh_settings, &baked_nav_mesh.settings) {
+        warn!("Baked nav-mesh was generated with different settings, it won't be loaded.");
+        return false;
+    }
+
+    let generation = {
+        let mut generation_ticker = world.resource_mut::<GenerationTicker>();
+        generation_ticker.0 += 1;
+
+        generation_ticker.0
+    };
+
+    let nav_mesh = world.resource::<NavMesh>().get();
+    let baked_tiles = world.resource::<BakedTileCache>().tiles.clone();
+    let (Ok(mut nav_mesh), Ok(mut baked_tiles)) = (nav_mesh.write(), baked_tiles.write()) else {
+        error!("Nav-Mesh lock has been poisoned. Generation can no longer be continued.");
+        return false;
+    };
+
+    let mut loaded_tiles = Vec::with_capacity(baked_nav_mesh.tiles.len());
+    for BakedTile { tile_coord, tile } in baked_nav_mesh.tiles {
+        if let Some(input_hash) = tile.input_hash {
+            baked_tiles.insert(
+                tile_coord,
+                LoadedBakedTile {
+                    generation,
+                    input_hash,
+                    mismatches_left: MAX_BAKED_TILE_MISMATCHES,
+                    tile: tile.clone(),
+                },
+            );
+        } else {
+            baked_tiles.remove(&tile_coord);
+        }
+
+        nav_mesh.tile_generations.insert(tile_coord, generation);
+        nav_mesh.add_tile(tile_coord, tile, &nav_mesh_settings);
+
+        loaded_tiles.push(TileGenerated(tile_coord));
+    }
+    drop((nav_mesh, baked_tiles));
+
+    world.send_event_batch(loaded_tiles);
+
+    true
+}
+
+/// Returns true if tiles generated with ``a`` & ``b`` are identical. Ignores settings that only affect when & how many tiles are generated.
+fn generates_same_tiles(a: &NavMeshSettings, b: &NavMeshSettings) -> bool {
+    a.cell_width == b.cell_width
+        && a.cell_height == b.cell_height
+        && a.tile_width == b.tile_width
+        && a.world_bounds == b.world_bounds
+        && a.world_bottom_bound == b.world_bottom_bound
+        && a.max_traversable_slope_radians == b.max_traversable_slope_radians
+        && a.walkable_height == b.walkable_height
+        && a.walkable_radius == b.walkable_radius
+        && a.step_height == b.step_height
+        && a.min_region_area == b.min_region_area
+        && a.max_region_area_to_merge_into == b.max_region_area_to_merge_into
+        && a.max_edge_length == b.max_edge_length
+        && a.max_contour_simplification_error == b.max_contour_simplification_error
+        && a.jump_links == b.jump_links
+        && a.partitioning == b.partitioning
+        && a.span_filters == b.span_filters
+        && a.median_area_filter == b.median_area_filter
+        && a.tessellation == b.tessellation
+        && a.deterministic == b.deterministic
+}
+
+/// Progress reported by [bake_nav_mesh] whenever tiles finish generating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BakeProgress {
+    /// Tiles generated since the bake started.
+    pub generated_tiles: usize,
+    /// Tiles waiting to be generated or currently generating.
+    pub remaining_tiles: usize,
+}
+
+/// Generates every dirty tile from the current state of ``world``, blocking until the nav-mesh is complete. Returns the number of tiles generated.
+///
+/// Requires the plugin to be added with [crate::OxidizedNavigationPlugin::with_bake_schedule], otherwise nothing is generated.
+///
+/// ``on_progress`` is called whenever tiles finish generating. [TileGenerated] events are sent as usual & can be read after the bake.
+///
+/// [NavMeshSettings::affector_debounce] is ignored. With [NavMeshSettings::streaming] only tiles around the current streaming anchors & tiles that are already loaded are generated.
+///
+/// **Note:** ``GlobalTransform`` is only updated in ``PostUpdate``, update the app once after spawning the world before baking it.
+pub fn bake_nav_mesh(world: &mut World, mut on_progress: impl FnMut(BakeProgress)) -> usize {
+    if !world.resource::<Schedules>().contains(NavMeshSchedule) {
+        error!(
+            "Baking requires OxidizedNavigationPlugin::with_bake_schedule. Nothing was generated."
+        );
+        return 0;
+    }
+
+    let mut tile_generated = world
+        .resource::<Events<TileGenerated>>()
+        .get_reader_current();
+
+    let mut generated_tiles = 0;
+    loop {
+        world.resource_mut::<DebouncedTiles>().0.clear();
+        world.run_schedule(NavMeshSchedule);
+
+        let generated = tile_generated
+            .read(world.resource::<Events<TileGenerated>>())
+            .count();
+        let remaining_tiles = get_remaining_tiles(world);
+
+        if generated > 0 {
+            generated_tiles += generated;
+
+            on_progress(BakeProgress {
+                generated_tiles,
+                remaining_tiles,
+            });
+        }
+
+        if remaining_tiles == 0 {
+            return generated_tiles;
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Returns the number of tiles that are dirty & can be generated or are currently generating.
+fn get_remaining_tiles(world: &World) -> usize {
+    let dirty_tiles = &world.resource::<DirtyTiles>().0;
+    let waiting_tiles = if world.resource::<NavMeshSettings>().streaming.is_some() {
+        let streamed_tiles = world.resource::<StreamedTiles>();
+
+        dirty_tiles
+            .iter()
+            .filter(|tile_coord| {
+                streamed_tiles.in_range.contains(*tile_coord)
+                    || streamed_tiles.loaded.contains(*tile_coord)
+            })
+            .count()
+    } else {
+        dirty_tiles.len()
+    };
+
+    waiting_tiles
+        + world.resource::<DirtyObstacleTiles>().0.len()
+        + world.resource::<ActiveGenerationTasks>().len()
+}
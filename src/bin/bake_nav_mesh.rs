@@ -0,0 +1,321 @@
+//! Bakes a nav-mesh from level geometry without running the game.
+//!
+//! ```text
+//! bake_nav_mesh <SETTINGS> <OUTPUT> <GEOMETRY>...
+//! ```
+//!
+//! * ``SETTINGS`` is a TOML file containing every field of ``NavMeshSettings``, optional fields can be left out.
+//! * ``OUTPUT`` is where the ``BakedNavMesh`` is written, encoded with ``bincode``. A summary of the bake is written next to it with the extension ``report.toml``.
+//! * ``GEOMETRY`` are glTF (``.gltf``, ``.glb``) or OBJ (``.obj``) files. All of their triangles are given ``Area(0)``.
+//!
+//! Triangles are sorted into the tiles they overlap up front & tiles are generated in parallel, only tiles with geometry within their bounds are generated.
+//!
+//! **Note:** Only plain triangle geometry is baked. Heightfields, ``NavMeshObstacle``s, ``NavMeshAreaVolume``s & ``NavMeshBlocker``s only exist in a running app
+//! & are never part of the baked tiles. ``load_baked_nav_mesh`` only checks the settings, so tiles of a scene using them are loaded as baked & stay that way until
+//! they're generated again, which happens as soon as their affectors are processed. Bake such scenes in-app with ``bake::bake_nav_mesh`` instead.
+use std::{
+    error::Error,
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Instant,
+};
+
+use bevy::{
+    math::{Mat4, Vec2, Vec3, Vec3Swizzles},
+    prelude::{Transform, UVec2},
+    tasks::{ComputeTaskPool, TaskPool},
+    utils::HashMap,
+};
+use nalgebra::Point3;
+use oxidized_navigation::{
+    bake::{BakedNavMesh, BakedTile},
+    build_tile_sync,
+    conversion::{GeometryCollection, GeometryToConvert},
+    Area, NavMeshSettings,
+};
+use serde::Serialize;
+
+const USAGE: &str = "usage: bake_nav_mesh <SETTINGS> <OUTPUT> <GEOMETRY>...
+
+Only plain triangle geometry from glTF & OBJ files is baked. Heightfields, obstacles, area volumes & blockers aren't included, bake scenes using them in-app with bake::bake_nav_mesh.";
+
+/// A triangle mesh in world space.
+struct LevelMesh {
+    vertices: Vec<Vec3>,
+    triangles: Vec<[u32; 3]>,
+}
+
+/// Summary of the bake written next to the nav-mesh.
+#[derive(Serialize)]
+struct BakeReport {
+    input_files: usize,
+    input_triangles: usize,
+    tiles: usize,
+    empty_tiles: usize,
+    polygons: usize,
+    vertices: usize,
+    off_mesh_links: usize,
+    bake_seconds: f64,
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
+    let mut args = std::env::args_os().skip(1);
+    let settings_path = PathBuf::from(args.next().ok_or(USAGE)?);
+    let output_path = PathBuf::from(args.next().ok_or(USAGE)?);
+    let geometry_paths: Vec<PathBuf> = args.map(PathBuf::from).collect();
+    if geometry_paths.is_empty() {
+        return Err(USAGE.into());
+    }
+
+    let settings: NavMeshSettings = toml::from_str(&fs::read_to_string(&settings_path)?)
+        .map_err(|error| format!("{}: {error}", settings_path.display()))?;
+
+    let mut meshes = Vec::new();
+    for path in &geometry_paths {
+        load_geometry(path, &mut meshes).map_err(|error| format!("{}: {error}", path.display()))?;
+    }
+
+    let start = Instant::now();
+    let tiles = bake_tiles(&meshes, &settings)?;
+    let bake_seconds = start.elapsed().as_secs_f64();
+
+    let report = BakeReport {
+        input_files: geometry_paths.len(),
+        input_triangles: meshes.iter().map(|mesh| mesh.triangles.len()).sum(),
+        tiles: tiles.len(),
+        empty_tiles: tiles
+            .iter()
+            .filter(|baked| baked.tile.polygons.is_empty())
+            .count(),
+        polygons: tiles.iter().map(|baked| baked.tile.polygons.len()).sum(),
+        vertices: tiles.iter().map(|baked| baked.tile.vertices.len()).sum(),
+        off_mesh_links: tiles
+            .iter()
+            .map(|baked| baked.tile.off_mesh_links.len())
+            .sum(),
+        bake_seconds,
+    };
+
+    let baked_nav_mesh = BakedNavMesh { settings, tiles };
+    bincode::serialize_into(BufWriter::new(File::create(&output_path)?), &baked_nav_mesh)?;
+
+    let report = toml::to_string(&report)?;
+    fs::write(output_path.with_extension("report.toml"), &report)?;
+    print!("{report}");
+
+    Ok(())
+}
+
+/// Loads every triangle mesh in the file at ``path`` into ``meshes``.
+fn load_geometry(path: &Path, meshes: &mut Vec<LevelMesh>) -> Result<(), Box<dyn Error>> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+
+    match extension.as_deref() {
+        Some("gltf" | "glb") => {
+            let (document, buffers, _) = gltf::import(path)?;
+
+            let scene = document
+                .default_scene()
+                .or_else(|| document.scenes().next())
+                .ok_or("file contains no scenes")?;
+            for node in scene.nodes() {
+                load_gltf_node(node, Mat4::IDENTITY, &buffers, meshes);
+            }
+        }
+        Some("obj") => {
+            let (models, _) = tobj::load_obj(path, &tobj::GPU_LOAD_OPTIONS)?;
+
+            for model in models {
+                let vertices = model
+                    .mesh
+                    .positions
+                    .chunks_exact(3)
+                    .map(Vec3::from_slice)
+                    .collect();
+                let triangles = model
+                    .mesh
+                    .indices
+                    .chunks_exact(3)
+                    .map(|indices| [indices[0], indices[1], indices[2]])
+                    .collect();
+
+                meshes.push(LevelMesh {
+                    vertices,
+                    triangles,
+                });
+            }
+        }
+        _ => return Err("unsupported file type, expected .gltf, .glb or .obj".into()),
+    }
+
+    Ok(())
+}
+
+fn load_gltf_node(
+    node: gltf::Node,
+    parent_transform: Mat4,
+    buffers: &[gltf::buffer::Data],
+    meshes: &mut Vec<LevelMesh>,
+) {
+    let transform = parent_transform * Mat4::from_cols_array_2d(&node.transform().matrix());
+    // Mirroring transforms flip the winding of the triangles, which would make them face down.
+    let flip_winding = transform.determinant() < 0.0;
+
+    if let Some(mesh) = node.mesh() {
+        for primitive in mesh.primitives() {
+            if primitive.mode() != gltf::mesh::Mode::Triangles {
+                continue;
+            }
+
+            let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+            let Some(positions) = reader.read_positions() else {
+                continue;
+            };
+            let vertices: Vec<Vec3> = positions
+                .map(|position| transform.transform_point3(Vec3::from(position)))
+                .collect();
+
+            let indices: Vec<u32> = match reader.read_indices() {
+                Some(indices) => indices.into_u32().collect(),
+                None => (0..vertices.len() as u32).collect(),
+            };
+            let triangles = indices
+                .chunks_exact(3)
+                .map(|indices| {
+                    if flip_winding {
+                        [indices[0], indices[2], indices[1]]
+                    } else {
+                        [indices[0], indices[1], indices[2]]
+                    }
+                })
+                .collect();
+
+            meshes.push(LevelMesh {
+                vertices,
+                triangles,
+            });
+        }
+    }
+
+    for child in node.children() {
+        load_gltf_node(child, transform, buffers, meshes);
+    }
+}
+
+/// Generates every tile containing geometry in parallel.
+fn bake_tiles(
+    meshes: &[LevelMesh],
+    settings: &NavMeshSettings,
+) -> Result<Vec<BakedTile>, Box<dyn Error>> {
+    let tile_triangles = bin_triangles(meshes, settings);
+    if tile_triangles.is_empty() {
+        return Err("no geometry within the world bounds".into());
+    }
+
+    let thread_pool = ComputeTaskPool::get_or_init(TaskPool::default);
+
+    let mut tiles = thread_pool.scope(|scope| {
+        for (tile_coord, triangles) in tile_triangles.iter() {
+            scope.spawn(async move {
+                let geometry_collections = vec![get_tile_geometry(meshes, triangles)];
+                let tile =
+                    build_tile_sync(geometry_collections, *tile_coord, Box::default(), settings);
+
+                BakedTile {
+                    tile_coord: *tile_coord,
+                    tile,
+                }
+            });
+        }
+    });
+    tiles.sort_by_key(|baked| (baked.tile_coord.y, baked.tile_coord.x));
+
+    Ok(tiles)
+}
+
+/// Returns the triangles overlapping each tile, including its border, as (mesh index, triangle index).
+///
+/// Tiles only copy the triangles they need, a large mesh like terrain isn't copied once per tile.
+fn bin_triangles(
+    meshes: &[LevelMesh],
+    settings: &NavMeshSettings,
+) -> HashMap<UVec2, Vec<(u32, u32)>> {
+    let border_size = settings.get_border_size();
+
+    let mut tile_triangles: HashMap<UVec2, Vec<(u32, u32)>> = HashMap::new();
+    for (mesh_index, mesh) in meshes.iter().enumerate() {
+        for (triangle_index, triangle) in mesh.triangles.iter().enumerate() {
+            let (min, max) = triangle.iter().fold(
+                (Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)),
+                |(min, max), index| {
+                    let vertex = mesh.vertices[*index as usize].xz();
+                    (min.min(vertex), max.max(vertex))
+                },
+            );
+            let Some((min_tile, max_tile)) =
+                settings.get_tiles_overlapping(min - border_size, max + border_size)
+            else {
+                continue;
+            };
+
+            for x in min_tile.x..=max_tile.x {
+                for y in min_tile.y..=max_tile.y {
+                    tile_triangles
+                        .entry(UVec2::new(x, y))
+                        .or_default()
+                        .push((mesh_index as u32, triangle_index as u32));
+                }
+            }
+        }
+    }
+
+    tile_triangles
+}
+
+/// Copies ``triangles`` into a single mesh for generating a tile.
+fn get_tile_geometry(meshes: &[LevelMesh], triangles: &[(u32, u32)]) -> GeometryCollection {
+    let mut vertex_indices: HashMap<(u32, u32), u32> = HashMap::new();
+    let mut vertices = Vec::new();
+
+    let triangles: Box<[[u32; 3]]> = triangles
+        .iter()
+        .map(|(mesh_index, triangle_index)| {
+            let mesh = &meshes[*mesh_index as usize];
+
+            mesh.triangles[*triangle_index as usize].map(|index| {
+                *vertex_indices
+                    .entry((*mesh_index, index))
+                    .or_insert_with(|| {
+                        vertices.push(Point3::from(mesh.vertices[index as usize]));
+
+                        vertices.len() as u32 - 1
+                    })
+            })
+        })
+        .collect();
+
+    GeometryCollection {
+        transform: Transform::IDENTITY,
+        geometry_to_convert: GeometryToConvert::ParryTriMesh(
+            vertices.into_boxed_slice(),
+            triangles,
+        ),
+        area: Some(Area(0)),
+        tessellation: None,
+    }
+}
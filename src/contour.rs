@@ -41,7 +41,7 @@ struct ContourRegion {
     holes: Vec<ContourHole>,
 }
 
-pub fn build_contours(open_tile: OpenTile, nav_mesh_settings: &NavMeshSettings) -> ContourSet {
+pub fn build_contours(open_tile: &OpenTile, nav_mesh_settings: &NavMeshSettings) -> ContourSet {
     let max_contours = open_tile.max_regions.max(8);
     let mut contour_set = ContourSet {
         contours: Vec::with_capacity(max_contours.into()),
@@ -95,7 +95,7 @@ pub fn build_contours(open_tile: OpenTile, nav_mesh_settings: &NavMeshSettings)
             walk_contour(
                 cell_index,
                 span_index,
-                &open_tile,
+                open_tile,
                 nav_mesh_settings,
                 &mut boundry_flags,
                 &mut vertices,
@@ -423,7 +423,7 @@ fn walk_contour(
                 2 => column + 1,
                 _ => column,
             } as u32;
-            let py = height as u32;
+            let py = height;
             let pz = match dir {
                 0 => row + 1,
                 1 => row + 1,
@@ -457,7 +457,7 @@ fn get_corner_height(
     tile: &OpenTile,
     nav_mesh_settings: &NavMeshSettings,
     dir: u8,
-) -> u16 {
+) -> u32 {
     let tile_side = nav_mesh_settings.get_tile_side_with_border();
     let next_dir = (dir + 1) & 0x3;
     let mut regions = [0; 4];
@@ -1,16 +1,20 @@
-use bevy::prelude::{Transform, Vec3};
+use std::f32::consts::TAU;
+
+use bevy::prelude::{Reflect, Transform, Vec3};
 use crate::parry::parry3d::{
     math::Real,
     na::Point3,
     shape::{Ball, Capsule, Cone, Cuboid, Cylinder, Triangle},
 };
 
-use crate::{heightfields::TriangleCollection, Area};
+use crate::{heightfields::TriangleCollection, Area, NavMeshSettings};
 
 pub struct GeometryCollection {
     pub transform: Transform,
     pub geometry_to_convert: GeometryToConvert,
     pub area: Option<Area>,
+    /// Overrides [NavMeshSettings::tessellation] for this collection.
+    pub tessellation: Option<Tessellation>,
 }
 
 pub enum ColliderType {
@@ -32,30 +36,94 @@ pub(super) enum Triangles {
     TriMesh(Box<[Vec3]>, Box<[[u32; 3]]>),
 }
 
-const SUBDIVISIONS: u32 = 5;
+/// How many subdivisions are used when turning curved colliders (balls, capsules, cylinders & cones) into triangles.
+///
+/// At least [Tessellation::MIN_SUBDIVISIONS] subdivisions are always used, fewer would give degenerate shapes.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum Tessellation {
+    /// Always use this many subdivisions. Clamped to at least [Tessellation::MIN_SUBDIVISIONS].
+    Fixed(u32),
+    /// Subdivide the shape's circumference into segments roughly ``segment_length`` cell_width(s) long, using between ``min`` & ``max`` subdivisions.
+    Adaptive {
+        segment_length: f32,
+        /// Clamped to at least [Tessellation::MIN_SUBDIVISIONS].
+        min: u32,
+        max: u32,
+    },
+}
+impl Default for Tessellation {
+    fn default() -> Self {
+        Self::Fixed(5)
+    }
+}
+impl Tessellation {
+    /// Fewest subdivisions a curved shape is turned into triangles with, a circle needs at least three sides.
+    pub const MIN_SUBDIVISIONS: u32 = 3;
+
+    /// Returns the number of subdivisions for a shape with a world space ``radius``.
+    pub fn subdivisions(&self, radius: f32, cell_width: f32) -> u32 {
+        let subdivisions = match *self {
+            Tessellation::Fixed(subdivisions) => subdivisions,
+            Tessellation::Adaptive {
+                segment_length,
+                min,
+                max,
+            } => {
+                let segments = (TAU * radius / (segment_length * cell_width)).ceil() as u32;
+
+                segments.min(max).max(min)
+            }
+        };
+
+        subdivisions.max(Self::MIN_SUBDIVISIONS)
+    }
+}
 
 pub(super) fn convert_geometry_collections(
     geometry_collections: Vec<GeometryCollection>,
+    nav_mesh_settings: &NavMeshSettings,
 ) -> Box<[TriangleCollection]> {
     geometry_collections
         .into_iter()
-        .map(|geometry_collection| TriangleCollection {
-            transform: geometry_collection.transform,
-            triangles: convert_geometry(geometry_collection.geometry_to_convert),
-            area: geometry_collection.area,
+        .map(|geometry_collection| {
+            let tessellation = geometry_collection
+                .tessellation
+                .unwrap_or(nav_mesh_settings.tessellation);
+            let scale = geometry_collection.transform.scale.abs().max_element();
+
+            TriangleCollection {
+                transform: geometry_collection.transform,
+                triangles: convert_geometry(geometry_collection.geometry_to_convert, |radius| {
+                    tessellation.subdivisions(radius * scale, nav_mesh_settings.cell_width)
+                }),
+                area: geometry_collection.area,
+            }
         })
         .collect()
 }
 
-pub(super) fn convert_geometry(geometry_to_convert: GeometryToConvert) -> Triangles {
+/// Converts the geometry into triangles. ``subdivisions`` returns the subdivisions to use for a curved shape of the given local radius.
+pub(super) fn convert_geometry(
+    geometry_to_convert: GeometryToConvert,
+    subdivisions: impl Fn(Real) -> u32,
+) -> Triangles {
     match geometry_to_convert {
         GeometryToConvert::Collider(collider) => {
             let (vertices, triangles) = match collider {
                 ColliderType::Cuboid(cuboid) => cuboid.to_trimesh(),
-                ColliderType::Ball(ball) => ball.to_trimesh(SUBDIVISIONS, SUBDIVISIONS),
-                ColliderType::Capsule(capsule) => capsule.to_trimesh(SUBDIVISIONS, SUBDIVISIONS),
-                ColliderType::Cylinder(cylinder) => cylinder.to_trimesh(SUBDIVISIONS),
-                ColliderType::Cone(cone) => cone.to_trimesh(SUBDIVISIONS),
+                ColliderType::Ball(ball) => {
+                    let subdivisions = subdivisions(ball.radius);
+                    ball.to_trimesh(subdivisions, subdivisions)
+                }
+                ColliderType::Capsule(capsule) => {
+                    let subdivisions = subdivisions(capsule.radius);
+                    capsule.to_trimesh(subdivisions, subdivisions)
+                }
+                ColliderType::Cylinder(cylinder) => {
+                    cylinder.to_trimesh(subdivisions(cylinder.radius))
+                }
+                ColliderType::Cone(cone) => cone.to_trimesh(subdivisions(cone.radius)),
                 ColliderType::Triangle(triangle) => {
                     return Triangles::Triangle(
                         triangle
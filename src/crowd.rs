@@ -0,0 +1,658 @@
+//! Module for crowd simulation on top of the nav-mesh.
+//!
+//! Agents with a [CrowdAgent] component follow a path to their target & avoid each other using ORCA (Optimal Reciprocal Collision Avoidance). Wall edges of the nav-mesh are treated as static obstacles to keep agents on it.
+//!
+//! Agents are moved by changing their ``Transform`` directly, so they should not have a parent.
+use bevy::{
+    prelude::{
+        App, Component, Entity, EventWriter, IVec2, IntoSystemConfigs, Local, Plugin, Query, Rect,
+        Reflect, ReflectComponent, Res, Resource, Time, Transform, Update, Vec2, Vec3,
+        Vec3Swizzles,
+    },
+    utils::HashMap,
+};
+use smallvec::SmallVec;
+
+use crate::{
+    query::find_path, tiles::PolygonFilter, NavMesh, NavMeshSettings, OutOfWorldBounds,
+    OxidizedNavigation,
+};
+
+const EPSILON: f32 = 0.00001;
+
+pub struct OxidizedNavigationCrowdPlugin {
+    pub settings: CrowdSettings,
+}
+impl OxidizedNavigationCrowdPlugin {
+    #[must_use]
+    pub fn new(settings: CrowdSettings) -> Self {
+        Self { settings }
+    }
+}
+impl Plugin for OxidizedNavigationCrowdPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.settings.clone());
+
+        app.add_systems(
+            Update,
+            (
+                update_crowd_agent_paths_system,
+                update_crowd_agent_velocities_system,
+                move_crowd_agents_system,
+            )
+                .chain()
+                .after(OxidizedNavigation::Main),
+        );
+
+        app.register_type::<CrowdAgent>();
+    }
+}
+
+/// Settings for the crowd simulation.
+#[derive(Resource, Clone, Debug)]
+pub struct CrowdSettings {
+    /// How far ahead in time, in seconds, agents avoid collisions with each other.
+    ///
+    /// Larger values make agents react earlier but also more cautiously.
+    pub time_horizon: f32,
+    /// How far ahead in time, in seconds, agents avoid collisions with nav-mesh walls.
+    pub obstacle_time_horizon: f32,
+    /// Max distance to other agents that are taken into account, in world units.
+    pub neighbour_distance: f32,
+    /// Max number of other agents taken into account. The closest ones are chosen.
+    pub max_neighbours: usize,
+    /// Max vertical distance between an agent & the nav-mesh (or another agent) for it to be considered, in world units.
+    pub height_tolerance: f32,
+    /// Distance from a path corner at which an agent moves on to the next one, in world units.
+    pub corner_tolerance: f32,
+    /// Radius used to find the start & end polygons when finding a path. See [find_path].
+    pub position_search_radius: Option<f32>,
+    /// Filter for which polygons agents may cross. See [find_path].
+    pub polygon_filter: Option<PolygonFilter>,
+}
+impl Default for CrowdSettings {
+    fn default() -> Self {
+        Self {
+            time_horizon: 2.0,
+            obstacle_time_horizon: 0.5,
+            neighbour_distance: 5.0,
+            max_neighbours: 10,
+            height_tolerance: 1.0,
+            corner_tolerance: 0.25,
+            position_search_radius: None,
+            polygon_filter: None,
+        }
+    }
+}
+
+/// Component for entities taking part in the crowd simulation.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct CrowdAgent {
+    /// Radius of the agent, in world units.
+    pub radius: f32,
+    /// Max speed of the agent, in world units per second.
+    pub max_speed: f32,
+    /// Max change in velocity, in world units per second squared.
+    pub max_acceleration: f32,
+    /// Position the agent is moving towards. Setting this to ``None`` makes the agent stop.
+    pub target: Option<Vec3>,
+    /// Current velocity of the agent. Written by the crowd systems.
+    pub velocity: Vec3,
+    /// Remaining corners of the path towards ``path_target``.
+    #[reflect(ignore)]
+    path: Vec<Vec3>,
+    /// The target the current path was found for.
+    #[reflect(ignore)]
+    path_target: Option<Vec3>,
+}
+impl Default for CrowdAgent {
+    fn default() -> Self {
+        Self::new(0.5, 3.5, 8.0)
+    }
+}
+impl CrowdAgent {
+    pub fn new(radius: f32, max_speed: f32, max_acceleration: f32) -> Self {
+        Self {
+            radius,
+            max_speed,
+            max_acceleration,
+            target: None,
+            velocity: Vec3::ZERO,
+            path: Vec::new(),
+            path_target: None,
+        }
+    }
+
+    /// Setter for [`CrowdAgent::target`]
+    pub fn with_target(mut self, target: Vec3) -> Self {
+        self.target = Some(target);
+
+        self
+    }
+
+    /// Returns the remaining corners of the agent's path.
+    pub fn path(&self) -> &[Vec3] {
+        &self.path
+    }
+}
+
+/// Half-plane of allowed velocities. Velocities to the left of ``direction`` are allowed.
+#[derive(Clone, Copy, Debug)]
+struct OrcaLine {
+    point: Vec2,
+    direction: Vec2,
+}
+
+struct AgentSnapshot {
+    entity: Entity,
+    position: Vec3,
+    velocity: Vec2,
+    radius: f32,
+}
+
+fn update_crowd_agent_paths_system(
+    nav_mesh_settings: Res<NavMeshSettings>,
+    crowd_settings: Res<CrowdSettings>,
+    nav_mesh: Res<NavMesh>,
+    mut out_of_world_bounds: EventWriter<OutOfWorldBounds>,
+    mut query: Query<(Entity, &mut CrowdAgent, &Transform)>,
+) {
+    let mut agents = query
+        .iter_mut()
+        .filter(|(_, agent, _)| agent.target != agent.path_target)
+        .peekable();
+    if agents.peek().is_none() {
+        return;
+    }
+
+    let nav_mesh_lock = nav_mesh.get();
+    let Ok(nav_mesh) = nav_mesh_lock.read() else {
+        return;
+    };
+
+    for (entity, mut agent, transform) in agents {
+        agent.path_target = agent.target;
+        agent.path.clear();
+
+        let Some(target) = agent.target else {
+            continue;
+        };
+
+        for position in [transform.translation.xz(), target.xz()] {
+            if !nav_mesh_settings.is_within_world_bounds(position) {
+                out_of_world_bounds.send(OutOfWorldBounds {
+                    entity,
+                    bounds: Rect::from_corners(position, position),
+                });
+            }
+        }
+
+        // Agents without a path stand still until their target changes.
+        if let Ok(path) = find_path(
+            &nav_mesh,
+            &nav_mesh_settings,
+            transform.translation,
+            target,
+            crowd_settings.position_search_radius,
+            None,
+            crowd_settings.polygon_filter,
+        ) {
+            agent.path = path;
+        }
+    }
+}
+
+fn update_crowd_agent_velocities_system(
+    nav_mesh_settings: Res<NavMeshSettings>,
+    crowd_settings: Res<CrowdSettings>,
+    nav_mesh: Res<NavMesh>,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut CrowdAgent, &Transform)>,
+    mut grid: Local<HashMap<IVec2, SmallVec<[usize; 8]>>>,
+) {
+    let delta_time = time.delta_seconds();
+    if delta_time <= 0.0 {
+        return;
+    }
+
+    let agents: Vec<AgentSnapshot> = query
+        .iter()
+        .map(|(entity, agent, transform)| AgentSnapshot {
+            entity,
+            position: transform.translation,
+            velocity: agent.velocity.xz(),
+            radius: agent.radius,
+        })
+        .collect();
+    if agents.is_empty() {
+        return;
+    }
+
+    // Bucket agents into a grid so finding neighbours doesn't need to check every agent.
+    let cell_size = crowd_settings.neighbour_distance.max(EPSILON);
+    grid.clear();
+    for (i, agent) in agents.iter().enumerate() {
+        let cell = (agent.position.xz() / cell_size).floor().as_ivec2();
+        grid.entry(cell).or_default().push(i);
+    }
+
+    let nav_mesh_lock = nav_mesh.get();
+    let Ok(nav_mesh) = nav_mesh_lock.read() else {
+        return;
+    };
+
+    let mut neighbours = Vec::with_capacity(crowd_settings.max_neighbours);
+    let mut lines = Vec::new();
+    for (entity, mut agent, transform) in query.iter_mut() {
+        let position = transform.translation;
+
+        // Move on to the next corner when close enough to the current one.
+        while agent.path.first().is_some_and(|corner| {
+            corner.xz().distance(position.xz()) <= crowd_settings.corner_tolerance
+        }) {
+            agent.path.remove(0);
+        }
+
+        let preferred_velocity = match agent.path.first() {
+            Some(corner) => {
+                let to_corner = corner.xz() - position.xz();
+
+                if agent.path.len() == 1 {
+                    // Slow down when arriving at the final corner.
+                    (to_corner / delta_time).clamp_length_max(agent.max_speed)
+                } else {
+                    to_corner.normalize_or_zero() * agent.max_speed
+                }
+            }
+            None => Vec2::ZERO,
+        };
+
+        lines.clear();
+
+        // Nav-mesh walls.
+        let radius = agent.radius;
+        let wall_range = radius + agent.max_speed * crowd_settings.obstacle_time_horizon;
+        let half_extents = Vec3::new(wall_range, crowd_settings.height_tolerance, wall_range);
+        nav_mesh.find_walls_in_box(
+            &nav_mesh_settings,
+            position - half_extents,
+            position + half_extents,
+            crowd_settings.polygon_filter,
+            |a, b| {
+                if let Some(line) = compute_wall_line(
+                    position.xz(),
+                    radius,
+                    a.xz(),
+                    b.xz(),
+                    wall_range,
+                    crowd_settings.obstacle_time_horizon,
+                ) {
+                    lines.push(line);
+                }
+            },
+        );
+        let obstacle_line_count = lines.len();
+
+        // Other agents.
+        neighbours.clear();
+        let cell = (position.xz() / cell_size).floor().as_ivec2();
+        for x in -1..=1 {
+            for y in -1..=1 {
+                let Some(indices) = grid.get(&(cell + IVec2::new(x, y))) else {
+                    continue;
+                };
+
+                for other in indices.iter().map(|i| &agents[*i]) {
+                    if other.entity == entity
+                        || (other.position.y - position.y).abs() > crowd_settings.height_tolerance
+                    {
+                        continue;
+                    }
+
+                    let distance = other.position.xz().distance_squared(position.xz());
+                    if distance < crowd_settings.neighbour_distance.powi(2) {
+                        neighbours.push((distance, other));
+                    }
+                }
+            }
+        }
+        neighbours.sort_unstable_by(|(a, _), (b, _)| a.total_cmp(b));
+        neighbours.truncate(crowd_settings.max_neighbours);
+
+        for (_, other) in neighbours.iter() {
+            if let Some(line) = compute_agent_line(
+                position.xz(),
+                agent.velocity.xz(),
+                agent.radius,
+                other,
+                crowd_settings.time_horizon,
+                delta_time,
+            ) {
+                lines.push(line);
+            }
+        }
+
+        let mut new_velocity = Vec2::ZERO;
+        let line_fail = linear_program_2(
+            &lines,
+            agent.max_speed,
+            preferred_velocity,
+            false,
+            &mut new_velocity,
+        );
+        if line_fail < lines.len() {
+            linear_program_3(
+                &lines,
+                obstacle_line_count,
+                line_fail,
+                agent.max_speed,
+                &mut new_velocity,
+            );
+        }
+
+        let velocity_change = (new_velocity - agent.velocity.xz())
+            .clamp_length_max(agent.max_acceleration * delta_time);
+        let velocity = agent.velocity.xz() + velocity_change;
+
+        agent.velocity = Vec3::new(velocity.x, 0.0, velocity.y);
+    }
+}
+
+fn move_crowd_agents_system(
+    nav_mesh_settings: Res<NavMeshSettings>,
+    crowd_settings: Res<CrowdSettings>,
+    nav_mesh: Res<NavMesh>,
+    time: Res<Time>,
+    mut query: Query<(&CrowdAgent, &mut Transform)>,
+) {
+    let nav_mesh_lock = nav_mesh.get();
+    let Ok(nav_mesh) = nav_mesh_lock.read() else {
+        return;
+    };
+
+    for (agent, mut transform) in query.iter_mut() {
+        if agent.velocity == Vec3::ZERO {
+            continue;
+        }
+
+        let position = transform.translation + agent.velocity * time.delta_seconds();
+
+        // Keep the agent on the surface of the nav-mesh.
+        transform.translation = nav_mesh
+            .find_polygon_at_point(
+                &nav_mesh_settings,
+                position,
+                crowd_settings.height_tolerance,
+                crowd_settings.polygon_filter,
+            )
+            .or_else(|| {
+                nav_mesh.find_closest_polygon_in_box(
+                    &nav_mesh_settings,
+                    position,
+                    agent.radius + crowd_settings.height_tolerance,
+                    crowd_settings.polygon_filter,
+                )
+            })
+            .map_or(position, |(_, position)| position);
+    }
+}
+
+/// Creates a line keeping the agent from moving into the wall between ``a`` & ``b`` within ``time_horizon``.
+fn compute_wall_line(
+    position: Vec2,
+    radius: f32,
+    a: Vec2,
+    b: Vec2,
+    range: f32,
+    time_horizon: f32,
+) -> Option<OrcaLine> {
+    let edge = b - a;
+    let t = ((position - a).dot(edge) / edge.length_squared().max(EPSILON)).clamp(0.0, 1.0);
+    let closest = a + edge * t;
+
+    let offset = position - closest;
+    let distance = offset.length();
+    if distance > range || distance <= EPSILON {
+        return None;
+    }
+
+    // Normal pointing away from the wall. Velocities towards the wall are limited to what can be covered before touching it.
+    let normal = offset / distance;
+    let min_speed_along_normal = -(distance - radius) / time_horizon;
+
+    Some(OrcaLine {
+        point: normal * min_speed_along_normal,
+        direction: Vec2::new(normal.y, -normal.x),
+    })
+}
+
+/// Creates the ORCA line for avoiding ``other``, taking half of the responsibility for avoiding the collision.
+fn compute_agent_line(
+    position: Vec2,
+    velocity: Vec2,
+    radius: f32,
+    other: &AgentSnapshot,
+    time_horizon: f32,
+    delta_time: f32,
+) -> Option<OrcaLine> {
+    let relative_position = other.position.xz() - position;
+    let relative_velocity = velocity - other.velocity;
+    let distance_squared = relative_position.length_squared();
+    let combined_radius = radius + other.radius;
+    let combined_radius_squared = combined_radius.powi(2);
+
+    let (direction, u) = if distance_squared > combined_radius_squared {
+        // No collision yet.
+        let inverse_time_horizon = 1.0 / time_horizon;
+        let w = relative_velocity - inverse_time_horizon * relative_position;
+        let w_length_squared = w.length_squared();
+        let dot_product = w.dot(relative_position);
+
+        if dot_product < 0.0 && dot_product.powi(2) > combined_radius_squared * w_length_squared {
+            // Project on the cut-off circle.
+            let w_length = w_length_squared.sqrt();
+            let unit_w = w / w_length;
+
+            (
+                Vec2::new(unit_w.y, -unit_w.x),
+                (combined_radius * inverse_time_horizon - w_length) * unit_w,
+            )
+        } else {
+            // Project on the legs.
+            let leg = (distance_squared - combined_radius_squared).sqrt();
+
+            let direction = if relative_position.perp_dot(w) > 0.0 {
+                Vec2::new(
+                    relative_position.x * leg - relative_position.y * combined_radius,
+                    relative_position.x * combined_radius + relative_position.y * leg,
+                ) / distance_squared
+            } else {
+                -Vec2::new(
+                    relative_position.x * leg + relative_position.y * combined_radius,
+                    -relative_position.x * combined_radius + relative_position.y * leg,
+                ) / distance_squared
+            };
+
+            (
+                direction,
+                relative_velocity.dot(direction) * direction - relative_velocity,
+            )
+        }
+    } else {
+        // Already colliding, resolve it within this time step.
+        let inverse_time_step = 1.0 / delta_time;
+        let w = relative_velocity - inverse_time_step * relative_position;
+        let w_length = w.length();
+        if w_length <= EPSILON {
+            return None;
+        }
+        let unit_w = w / w_length;
+
+        (
+            Vec2::new(unit_w.y, -unit_w.x),
+            (combined_radius * inverse_time_step - w_length) * unit_w,
+        )
+    };
+
+    Some(OrcaLine {
+        point: velocity + 0.5 * u,
+        direction,
+    })
+}
+
+/// Solves a one-dimensional linear program on the line at ``line_index`` constrained by all previous lines & a circle of ``radius``.
+fn linear_program_1(
+    lines: &[OrcaLine],
+    line_index: usize,
+    radius: f32,
+    optimization_velocity: Vec2,
+    optimize_direction: bool,
+    result: &mut Vec2,
+) -> bool {
+    let line = lines[line_index];
+    let dot_product = line.point.dot(line.direction);
+    let discriminant = dot_product.powi(2) + radius.powi(2) - line.point.length_squared();
+    if discriminant < 0.0 {
+        // Max speed circle fully invalidates the line.
+        return false;
+    }
+
+    let discriminant_sqrt = discriminant.sqrt();
+    let mut t_left = -dot_product - discriminant_sqrt;
+    let mut t_right = -dot_product + discriminant_sqrt;
+
+    for other in lines.iter().take(line_index) {
+        let denominator = line.direction.perp_dot(other.direction);
+        let numerator = other.direction.perp_dot(line.point - other.point);
+
+        if denominator.abs() <= EPSILON {
+            // Lines are (almost) parallel.
+            if numerator < 0.0 {
+                return false;
+            }
+            continue;
+        }
+
+        let t = numerator / denominator;
+        if denominator >= 0.0 {
+            t_right = t_right.min(t);
+        } else {
+            t_left = t_left.max(t);
+        }
+
+        if t_left > t_right {
+            return false;
+        }
+    }
+
+    *result = if optimize_direction {
+        if optimization_velocity.dot(line.direction) > 0.0 {
+            line.point + t_right * line.direction
+        } else {
+            line.point + t_left * line.direction
+        }
+    } else {
+        let t = line.direction.dot(optimization_velocity - line.point);
+
+        line.point + t.clamp(t_left, t_right) * line.direction
+    };
+
+    true
+}
+
+/// Solves a two-dimensional linear program subject to ``lines`` & a circle of ``radius``.
+///
+/// Returns the index of the line it failed on, or the number of lines if successful.
+fn linear_program_2(
+    lines: &[OrcaLine],
+    radius: f32,
+    optimization_velocity: Vec2,
+    optimize_direction: bool,
+    result: &mut Vec2,
+) -> usize {
+    *result = if optimize_direction {
+        // Optimization velocity is a unit vector.
+        optimization_velocity * radius
+    } else {
+        optimization_velocity.clamp_length_max(radius)
+    };
+
+    for (i, line) in lines.iter().enumerate() {
+        if line.direction.perp_dot(line.point - *result) > 0.0 {
+            // Result doesn't satisfy this line.
+            let previous_result = *result;
+            if !linear_program_1(
+                lines,
+                i,
+                radius,
+                optimization_velocity,
+                optimize_direction,
+                result,
+            ) {
+                *result = previous_result;
+                return i;
+            }
+        }
+    }
+
+    lines.len()
+}
+
+/// Solves a three-dimensional linear program, used when [linear_program_2] fails. Finds the velocity that least violates the agent lines while respecting the obstacle lines.
+fn linear_program_3(
+    lines: &[OrcaLine],
+    obstacle_line_count: usize,
+    begin_line: usize,
+    radius: f32,
+    result: &mut Vec2,
+) {
+    let mut distance = 0.0;
+
+    for i in begin_line..lines.len() {
+        let line = lines[i];
+        if line.direction.perp_dot(line.point - *result) <= distance {
+            continue;
+        }
+
+        // Result doesn't satisfy the constraint of this line.
+        let mut projected_lines = lines[..obstacle_line_count].to_vec();
+        for other in &lines[obstacle_line_count..i] {
+            let determinant = line.direction.perp_dot(other.direction);
+
+            let point = if determinant.abs() <= EPSILON {
+                if line.direction.dot(other.direction) > 0.0 {
+                    // Lines point in the same direction.
+                    continue;
+                }
+
+                // Lines point in opposite directions.
+                0.5 * (line.point + other.point)
+            } else {
+                line.point
+                    + (other.direction.perp_dot(line.point - other.point) / determinant)
+                        * line.direction
+            };
+
+            projected_lines.push(OrcaLine {
+                point,
+                direction: (other.direction - line.direction).normalize_or_zero(),
+            });
+        }
+
+        let previous_result = *result;
+        if linear_program_2(
+            &projected_lines,
+            radius,
+            Vec2::new(-line.direction.y, line.direction.x),
+            true,
+            result,
+        ) < projected_lines.len()
+        {
+            // This should in principle not happen. The result is by definition already in the feasible region of this linear program. If it fails it's due to small floating point errors & the current result is kept.
+            *result = previous_result;
+        }
+
+        distance = line.direction.perp_dot(line.point - *result);
+    }
+}
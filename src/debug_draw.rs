@@ -63,6 +63,11 @@ fn draw_nav_mesh_system(nav_mesh: Res<NavMesh>, mut gizmos: Gizmos<NavigationGro
             for vertex in tile.vertices.iter() {
                 gizmos.line(*vertex, *vertex + Vec3::Y, tile_color);
             }
+
+            // Draw off-mesh links.
+            for link in tile.off_mesh_links.iter() {
+                gizmos.line(link.start, link.end, Color::srgb(1.0, 0.5, 0.0));
+            }
         }
     }
 }
@@ -0,0 +1,261 @@
+//! Module for exporting nav-mesh tiles to OBJ & glTF files, for inspecting them next to the source geometry in tools like Blender.
+//!
+//! Every tile becomes its own object (``tile_{x}_{y}``) & polygons are assigned a material per [Area] (``area_{area}``).
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{self, Write},
+};
+
+use bevy::prelude::{UVec2, Vec3};
+
+use crate::{
+    tiles::{NavMeshTile, Polygon},
+    Area,
+};
+
+/// Writes ``tiles`` to ``writer`` as an OBJ file.
+///
+/// ```no_run
+/// # use std::fs::File;
+/// # use oxidized_navigation::{export::write_obj, tiles::NavMeshTiles};
+/// # fn export(nav_mesh: &NavMeshTiles) -> std::io::Result<()> {
+/// let tiles = nav_mesh.get_tiles().iter().map(|(tile_coord, tile)| (*tile_coord, tile));
+/// write_obj(tiles, File::create("nav_mesh.obj")?)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn write_obj<'a>(
+    tiles: impl IntoIterator<Item = (UVec2, &'a NavMeshTile)>,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    // OBJ indices start at 1 & are shared between all objects in the file.
+    let mut vertex_offset = 1;
+
+    for (tile_coord, tile) in get_sorted_tiles(tiles) {
+        writeln!(writer, "o {}", get_tile_name(tile_coord))?;
+
+        for vertex in tile.vertices.iter() {
+            writeln!(writer, "v {} {} {}", vertex.x, vertex.y, vertex.z)?;
+        }
+
+        for (area, polygons) in get_polygons_by_area(tile) {
+            writeln!(writer, "usemtl {}", get_area_name(area))?;
+
+            for polygon in polygons {
+                let [a, b, c] = polygon.indices.map(|index| index + vertex_offset);
+                writeln!(writer, "f {a} {b} {c}")?;
+            }
+        }
+
+        vertex_offset += tile.vertices.len() as u32;
+    }
+
+    writer.flush()
+}
+
+/// Writes ``tiles`` to ``writer`` as a binary glTF (``.glb``) file.
+///
+/// Each tile is a node with a mesh containing one primitive per [Area].
+pub fn write_glb<'a>(
+    tiles: impl IntoIterator<Item = (UVec2, &'a NavMeshTile)>,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    let tiles = get_sorted_tiles(tiles);
+
+    let areas: BTreeSet<Area> = tiles
+        .iter()
+        .flat_map(|(_, tile)| tile.polygons.iter().map(|polygon| polygon.area))
+        .collect();
+    let materials: Vec<String> = areas
+        .iter()
+        .map(|area| format!(r#"{{"name":"{}"}}"#, get_area_name(*area)))
+        .collect();
+
+    let mut buffer = Vec::new();
+    let mut buffer_views = Vec::new();
+    let mut accessors = Vec::new();
+    let mut meshes = Vec::new();
+    let mut nodes = Vec::new();
+
+    for (tile_coord, tile) in tiles.iter() {
+        let (min, max) = tile.vertices.iter().fold(
+            (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)),
+            |(min, max), vertex| (min.min(*vertex), max.max(*vertex)),
+        );
+
+        let buffer_view = push_buffer_view(
+            &mut buffer,
+            &mut buffer_views,
+            tile.vertices
+                .iter()
+                .flat_map(|vertex| vertex.to_array())
+                .map(f32::to_le_bytes),
+            ARRAY_BUFFER,
+        );
+        let position_accessor = accessors.len();
+        accessors.push(format!(
+            r#"{{"bufferView":{buffer_view},"componentType":{FLOAT},"count":{},"type":"VEC3","min":[{},{},{}],"max":[{},{},{}]}}"#,
+            tile.vertices.len(),
+            min.x,
+            min.y,
+            min.z,
+            max.x,
+            max.y,
+            max.z,
+        ));
+
+        let primitives: Vec<String> = get_polygons_by_area(tile)
+            .into_iter()
+            .map(|(area, polygons)| {
+                let buffer_view = push_buffer_view(
+                    &mut buffer,
+                    &mut buffer_views,
+                    polygons
+                        .iter()
+                        .flat_map(|polygon| polygon.indices)
+                        .map(u32::to_le_bytes),
+                    ELEMENT_ARRAY_BUFFER,
+                );
+                let index_accessor = accessors.len();
+                accessors.push(format!(
+                    r#"{{"bufferView":{buffer_view},"componentType":{UNSIGNED_INT},"count":{},"type":"SCALAR"}}"#,
+                    polygons.len() * 3
+                ));
+
+                let material = areas.range(..area).count();
+                format!(
+                    r#"{{"attributes":{{"POSITION":{position_accessor}}},"indices":{index_accessor},"material":{material}}}"#
+                )
+            })
+            .collect();
+
+        nodes.push(format!(
+            r#"{{"name":"{}","mesh":{}}}"#,
+            get_tile_name(*tile_coord),
+            meshes.len()
+        ));
+        meshes.push(format!(r#"{{"primitives":[{}]}}"#, primitives.join(",")));
+    }
+
+    let scene_nodes: Vec<String> = (0..nodes.len()).map(|node| node.to_string()).collect();
+
+    // glTF doesn't allow empty arrays, leave them out when there's nothing to export.
+    let mut json = String::from(
+        r#"{"asset":{"version":"2.0","generator":"oxidized_navigation"},"scene":0,"scenes":[{"#,
+    );
+    if !scene_nodes.is_empty() {
+        push_json_array(&mut json, "nodes", &scene_nodes);
+    }
+    json.push_str("}]");
+    for (name, items) in [
+        ("nodes", &nodes),
+        ("meshes", &meshes),
+        ("materials", &materials),
+        ("accessors", &accessors),
+        ("bufferViews", &buffer_views),
+    ] {
+        if !items.is_empty() {
+            json.push(',');
+            push_json_array(&mut json, name, items);
+        }
+    }
+    if !buffer.is_empty() {
+        json.push_str(&format!(
+            r#","buffers":[{{"byteLength":{}}}]"#,
+            buffer.len()
+        ));
+    }
+    json.push('}');
+
+    // Chunks must be 4 byte aligned, the JSON chunk is padded with spaces.
+    let mut json = json.into_bytes();
+    json.resize(json.len().next_multiple_of(4), b' ');
+    buffer.resize(buffer.len().next_multiple_of(4), 0);
+
+    let mut length = GLB_HEADER_SIZE + GLB_CHUNK_HEADER_SIZE + json.len();
+    if !buffer.is_empty() {
+        length += GLB_CHUNK_HEADER_SIZE + buffer.len();
+    }
+
+    writer.write_all(b"glTF")?;
+    writer.write_all(&2u32.to_le_bytes())?;
+    writer.write_all(&(length as u32).to_le_bytes())?;
+
+    writer.write_all(&(json.len() as u32).to_le_bytes())?;
+    writer.write_all(b"JSON")?;
+    writer.write_all(&json)?;
+
+    if !buffer.is_empty() {
+        writer.write_all(&(buffer.len() as u32).to_le_bytes())?;
+        writer.write_all(b"BIN\0")?;
+        writer.write_all(&buffer)?;
+    }
+
+    writer.flush()
+}
+
+const GLB_HEADER_SIZE: usize = 12;
+const GLB_CHUNK_HEADER_SIZE: usize = 8;
+
+// glTF constants.
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+/// Returns the tiles with polygons, sorted by tile coordinate so exports are stable.
+fn get_sorted_tiles<'a>(
+    tiles: impl IntoIterator<Item = (UVec2, &'a NavMeshTile)>,
+) -> Vec<(UVec2, &'a NavMeshTile)> {
+    let mut tiles: Vec<_> = tiles
+        .into_iter()
+        .filter(|(_, tile)| !tile.polygons.is_empty())
+        .collect();
+    tiles.sort_by_key(|(tile_coord, _)| (tile_coord.y, tile_coord.x));
+
+    tiles
+}
+
+fn get_polygons_by_area(tile: &NavMeshTile) -> BTreeMap<Area, Vec<&Polygon>> {
+    let mut polygons_by_area: BTreeMap<Area, Vec<&Polygon>> = BTreeMap::new();
+    for polygon in tile.polygons.iter() {
+        polygons_by_area
+            .entry(polygon.area)
+            .or_default()
+            .push(polygon);
+    }
+
+    polygons_by_area
+}
+
+fn get_tile_name(tile_coord: UVec2) -> String {
+    format!("tile_{}_{}", tile_coord.x, tile_coord.y)
+}
+
+fn get_area_name(area: Area) -> String {
+    format!("area_{}", area.0)
+}
+
+/// Appends the little-endian ``values`` to ``buffer`` & adds a buffer view for them. Returns the index of the buffer view.
+fn push_buffer_view(
+    buffer: &mut Vec<u8>,
+    buffer_views: &mut Vec<String>,
+    values: impl Iterator<Item = [u8; 4]>,
+    target: u32,
+) -> usize {
+    let offset = buffer.len();
+    for value in values {
+        buffer.extend_from_slice(&value);
+    }
+
+    buffer_views.push(format!(
+        r#"{{"buffer":0,"byteOffset":{offset},"byteLength":{},"target":{target}}}"#,
+        buffer.len() - offset
+    ));
+
+    buffer_views.len() - 1
+}
+
+fn push_json_array(json: &mut String, name: &str, items: &[String]) {
+    json.push_str(&format!(r#""{name}":[{}]"#, items.join(",")));
+}
@@ -1,17 +1,24 @@
 use std::{cmp::Ordering, ops::Div, sync::Arc};
 
-use bevy::{math::Vec3A, prelude::*};
+use bevy::{
+    math::Vec3A,
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, TaskPool},
+};
 use crate::parry::parry3d::shape::HeightField;
 use smallvec::SmallVec;
 
-use crate::{conversion::Triangles, Area};
+use crate::{
+    conversion::Triangles, Area, NavMeshAreaVolume, NavMeshBlocker, NavMeshObstacle,
+    NavMeshVolumeShape,
+};
 
 use super::{get_neighbour_index, NavMeshSettings};
 
 #[derive(Default, Clone, Debug)]
 struct HeightSpan {
-    min: u16,
-    max: u16,
+    min: u32,
+    max: u32,
     traversable: bool,
     area: Option<Area>,
 }
@@ -21,9 +28,11 @@ struct VoxelCell {
     spans: SmallVec<[HeightSpan; 2]>, // Bottom to top.
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct VoxelizedTile {
     cells: Box<[VoxelCell]>, // len = tiles_along_width^2. Laid out X to Y
+    /// Hash of every triangle rasterized into the tile, see [hash_tile_geometry].
+    pub(super) input_hash: u64,
 }
 
 #[derive(Default, Clone, Debug)]
@@ -34,15 +43,15 @@ pub(super) struct OpenCell {
 // Like a HeightSpan but representing open walkable areas (empty space with floor & height >= walkable_height
 #[derive(Default, Clone, Copy, Debug)]
 pub(super) struct OpenSpan {
-    pub(super) min: u16,
-    pub(super) max: Option<u16>,
+    pub(super) min: u32,
+    pub(super) max: Option<u32>,
     pub(super) neighbours: [Option<u16>; 4],
     pub(super) tile_index: usize, // The index of this span in the whole tile.
     pub(super) region: u16,       // Region if non-zero.
-    area: Option<Area>, // TODO: Ideally we don't want store this here. It's only here to be copied over to [OpenTile::areas] & bumps up the OpenSpan size from 32b to 40b.
+    area: Option<Area>, // TODO: Ideally we don't want store this here. It's only here to be copied over to [OpenTile::areas] & bumps up the OpenSpan size from 40b to 48b.
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Clone, Debug)]
 pub struct OpenTile {
     pub(super) cells: Vec<OpenCell>, // len = tiles_along_width^2. Laid out X to Y
     pub(super) distances: Box<[u16]>, // Distances used in watershed. One per span. Use tile_index to go from span to distance.
@@ -50,6 +59,8 @@ pub struct OpenTile {
     pub(super) max_distance: u16,
     pub(super) span_count: usize, // Total spans in all cells.
     pub(super) max_regions: u16,
+    /// Hash of the geometry the tile was built from. ``None`` if blockers were applied to it.
+    pub(super) input_hash: Option<u64>,
 }
 
 pub(super) struct TriangleCollection {
@@ -64,6 +75,21 @@ pub struct HeightFieldCollection {
     pub area: Option<Area>,
 }
 
+pub(super) struct ObstacleCollection {
+    pub(super) transform: Transform,
+    pub(super) obstacle: NavMeshObstacle,
+}
+
+pub(super) struct AreaVolumeCollection {
+    pub(super) transform: Transform,
+    pub(super) area_volume: NavMeshAreaVolume,
+}
+
+pub(super) struct BlockerCollection {
+    pub(super) transform: Transform,
+    pub(super) blocker: NavMeshBlocker,
+}
+
 pub(super) fn build_heightfield_tile(
     tile_coord: UVec2,
     triangle_collections: &[TriangleCollection],
@@ -73,8 +99,130 @@ pub(super) fn build_heightfield_tile(
     let tile_side = nav_mesh_settings.get_tile_side_with_border();
     let mut voxel_tile = VoxelizedTile {
         cells: vec![VoxelCell::default(); tile_side.pow(2)].into_boxed_slice(),
+        input_hash: 0,
     };
 
+    rasterize_into_heightfield_tile(
+        &mut voxel_tile,
+        tile_coord,
+        triangle_collections,
+        heightfields,
+        nav_mesh_settings,
+    );
+
+    voxel_tile
+}
+
+/// Rasterizes the geometry into an existing [VoxelizedTile], merging with the spans already in it.
+///
+/// Used to add the non-static affectors on top of a cached static layer.
+///
+/// Large amounts of triangles are rasterized in parallel, with each task covering a band of rows. Every band goes over the triangles in the same order, so the result is identical to rasterizing them serially.
+pub(super) fn rasterize_into_heightfield_tile(
+    voxel_tile: &mut VoxelizedTile,
+    tile_coord: UVec2,
+    triangle_collections: &[TriangleCollection],
+    heightfields: &[Arc<HeightFieldCollection>],
+    nav_mesh_settings: &NavMeshSettings,
+) {
+    let tile_side = nav_mesh_settings.get_tile_side_with_border();
+
+    let triangles = gather_tile_triangles(
+        tile_coord,
+        triangle_collections,
+        heightfields,
+        nav_mesh_settings,
+    );
+    voxel_tile.input_hash = triangles
+        .iter()
+        .fold(voxel_tile.input_hash, |hash, triangle| {
+            hash.wrapping_add(hash_tile_triangle(triangle))
+        });
+
+    let thread_pool = AsyncComputeTaskPool::get_or_init(TaskPool::default);
+    let max_tasks = nav_mesh_settings
+        .max_rasterization_tasks
+        .map_or(thread_pool.thread_num(), |max_tasks| max_tasks.get().into());
+    let band_count = (triangles.len() / MIN_TRIANGLES_PER_RASTERIZATION_TASK)
+        .min(max_tasks)
+        .clamp(1, tile_side);
+
+    if band_count == 1 {
+        rasterize_band(&triangles, 0, &mut voxel_tile.cells, nav_mesh_settings);
+        return;
+    }
+
+    let rows_per_band = tile_side.div_ceil(band_count);
+    thread_pool.scope(|scope| {
+        let triangles = &triangles;
+        for (band, cells) in voxel_tile
+            .cells
+            .chunks_mut(rows_per_band * tile_side)
+            .enumerate()
+        {
+            scope.spawn(async move {
+                rasterize_band(triangles, band * rows_per_band, cells, nav_mesh_settings);
+            });
+        }
+    });
+}
+
+/// Tiles with fewer triangles than this per task are rasterized serially.
+const MIN_TRIANGLES_PER_RASTERIZATION_TASK: usize = 2048;
+
+/// A triangle relative to the origin of the tile (including border) it's rasterized into.
+struct TileTriangle {
+    vertices: [Vec3A; 3],
+    traversable: bool,
+    area: Option<Area>,
+}
+
+/// Returns a hash of the geometry rasterized into a tile, used to tell if a baked tile is still up to date.
+///
+/// Triangles are hashed individually & summed so the order of the affectors doesn't matter. The hash only changes if the triangles within the tile change, which means the static layer & the other affectors can be hashed separately & added together.
+pub(super) fn hash_tile_geometry(
+    tile_coord: UVec2,
+    triangle_collections: &[TriangleCollection],
+    heightfields: &[Arc<HeightFieldCollection>],
+    nav_mesh_settings: &NavMeshSettings,
+) -> u64 {
+    gather_tile_triangles(
+        tile_coord,
+        triangle_collections,
+        heightfields,
+        nav_mesh_settings,
+    )
+    .iter()
+    .fold(0, |hash, triangle| {
+        hash.wrapping_add(hash_tile_triangle(triangle))
+    })
+}
+
+/// FNV-1a hash of a triangle. Unlike the std hashers the result is the same across Rust versions, baked tiles are often generated by a different build than the one loading them.
+fn hash_tile_triangle(triangle: &TileTriangle) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    let area = triangle.area.map_or(u32::MAX, |area| u32::from(area.0));
+    triangle
+        .vertices
+        .iter()
+        .flat_map(|vertex| vertex.to_array().map(f32::to_bits))
+        .chain([u32::from(triangle.traversable), area])
+        .flat_map(u32::to_le_bytes)
+        .fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+        })
+}
+
+/// Transforms all triangles into tile space, skipping the ones entirely outside the tile on the XZ-plane.
+fn gather_tile_triangles(
+    tile_coord: UVec2,
+    triangle_collections: &[TriangleCollection],
+    heightfields: &[Arc<HeightFieldCollection>],
+    nav_mesh_settings: &NavMeshSettings,
+) -> Vec<TileTriangle> {
+    let tile_side = nav_mesh_settings.get_tile_side_with_border();
     let tile_max_bound = IVec3::new((tile_side - 1) as i32, 0, (tile_side - 1) as i32);
 
     let tile_origin = nav_mesh_settings.get_tile_origin_with_border(tile_coord);
@@ -84,6 +232,27 @@ pub(super) fn build_heightfield_tile(
         tile_origin.y,
     );
 
+    let mut triangles = Vec::new();
+    let mut push_triangle = |a: Vec3A, b: Vec3A, c: Vec3A, area: Option<Area>| {
+        let min_bound = a.min(b).min(c).div(nav_mesh_settings.cell_width).as_ivec3();
+        let max_bound = a.max(b).max(c).div(nav_mesh_settings.cell_width).as_ivec3();
+
+        // Check if triangle is completely outside the tile.
+        if max_bound.x < 0
+            || max_bound.z < 0
+            || min_bound.x > tile_max_bound.x
+            || min_bound.z > tile_max_bound.z
+        {
+            return;
+        }
+
+        triangles.push(TileTriangle {
+            vertices: [a, b, c],
+            traversable: is_triangle_traversable(a, b, c, nav_mesh_settings),
+            area,
+        });
+    };
+
     let mut translated_vertices = Vec::default();
 
     for collection in triangle_collections.iter() {
@@ -95,14 +264,10 @@ pub(super) fn build_heightfield_tile(
                 let translated_vertices =
                     vertices.map(|vertex| transform.transform_point(vertex) - tile_origin);
 
-                process_triangle(
+                push_triangle(
                     Vec3A::from(translated_vertices[0]),
                     Vec3A::from(translated_vertices[1]),
                     Vec3A::from(translated_vertices[2]),
-                    nav_mesh_settings,
-                    tile_max_bound,
-                    tile_side,
-                    &mut voxel_tile.cells,
                     collection.area,
                 );
             }
@@ -119,16 +284,7 @@ pub(super) fn build_heightfield_tile(
                     let b = Vec3A::from(translated_vertices[triangle[1] as usize]);
                     let c = Vec3A::from(translated_vertices[triangle[2] as usize]);
 
-                    process_triangle(
-                        a,
-                        b,
-                        c,
-                        nav_mesh_settings,
-                        tile_max_bound,
-                        tile_side,
-                        &mut voxel_tile.cells,
-                        collection.area,
-                    );
+                    push_triangle(a, b, c, collection.area);
                 }
             }
         }
@@ -152,48 +308,69 @@ pub(super) fn build_heightfield_tile(
                     - tile_origin,
             );
 
-            process_triangle(
-                a,
-                b,
-                c,
-                nav_mesh_settings,
-                tile_max_bound,
-                tile_side,
-                &mut voxel_tile.cells,
-                collection.area,
-            );
+            push_triangle(a, b, c, collection.area);
         }
     }
 
-    voxel_tile
+    triangles
 }
 
+/// Rasterizes ``triangles`` into ``voxel_cells``, the rows of the tile starting at ``first_row``.
+fn rasterize_band(
+    triangles: &[TileTriangle],
+    first_row: usize,
+    voxel_cells: &mut [VoxelCell],
+    nav_mesh_settings: &NavMeshSettings,
+) {
+    let tile_side = nav_mesh_settings.get_tile_side_with_border();
+    let row_count = voxel_cells.len() / tile_side;
+
+    let band_min_bound = IVec3::new(0, 0, first_row as i32);
+    let band_max_bound = IVec3::new(
+        (tile_side - 1) as i32,
+        0,
+        (first_row + row_count - 1) as i32,
+    );
+
+    for triangle in triangles.iter() {
+        process_triangle(
+            triangle,
+            nav_mesh_settings,
+            band_min_bound,
+            band_max_bound,
+            tile_side,
+            voxel_cells,
+        );
+    }
+}
+
+/// Rasterizes ``triangle`` into the rows from ``band_min_bound.z`` to ``band_max_bound.z``, which ``voxel_cells`` starts at.
 fn process_triangle(
-    a: Vec3A,
-    b: Vec3A,
-    c: Vec3A,
+    triangle: &TileTriangle,
     nav_mesh_settings: &NavMeshSettings,
-    tile_max_bound: IVec3,
+    band_min_bound: IVec3,
+    band_max_bound: IVec3,
     tile_side: usize,
     voxel_cells: &mut [VoxelCell],
-    area: Option<Area>,
 ) {
+    let [a, b, c] = triangle.vertices;
     let min_bound = a.min(b).min(c).div(nav_mesh_settings.cell_width).as_ivec3();
     let max_bound = a.max(b).max(c).div(nav_mesh_settings.cell_width).as_ivec3();
 
-    // Check if triangle is completely outside the tile.
-    if max_bound.x < 0
-        || max_bound.z < 0
-        || min_bound.x > tile_max_bound.x
-        || min_bound.z > tile_max_bound.z
+    // Check if triangle is completely outside the band.
+    if max_bound.x < band_min_bound.x
+        || max_bound.z < band_min_bound.z
+        || min_bound.x > band_max_bound.x
+        || min_bound.z > band_max_bound.z
     {
         return;
     }
 
-    let clamped_bound_min = min_bound.max(IVec3::ZERO);
-    let clamped_bound_max = max_bound.min(tile_max_bound);
-    let traversable = is_triangle_traversable(a, b, c, nav_mesh_settings);
-    let vertices = [a, b, c];
+    let clamped_bound_min = min_bound.max(band_min_bound);
+    let clamped_bound_max = max_bound.min(band_max_bound);
+    let traversable = triangle.traversable;
+    let area = triangle.area;
+    let vertices = triangle.vertices;
 
     // For cache reasons we go.
     // --> X
@@ -260,10 +437,10 @@ fn process_triangle(
                 continue;
             }
 
-            let min_height = (square_min_height / nav_mesh_settings.cell_height) as u16;
-            let max_height = (square_max_height / nav_mesh_settings.cell_height) as u16;
+            let min_height = (square_min_height / nav_mesh_settings.cell_height) as u32;
+            let max_height = (square_max_height / nav_mesh_settings.cell_height) as u32;
 
-            let index = x as usize + z as usize * tile_side;
+            let index = x as usize + (z - band_min_bound.z) as usize * tile_side;
             let cell = &mut voxel_cells[index];
 
             let mut new_span = HeightSpan {
@@ -402,9 +579,26 @@ fn divide_polygon(
 }
 
 pub fn build_open_heightfield_tile(
-    voxelized_tile: VoxelizedTile,
+    mut voxelized_tile: VoxelizedTile,
     nav_mesh_settings: &NavMeshSettings,
 ) -> OpenTile {
+    let span_filters = nav_mesh_settings.span_filters;
+    if span_filters.low_hanging_obstacles {
+        #[cfg(feature = "trace")]
+        let _span = info_span!("Filter low hanging obstacles").entered();
+        filter_low_hanging_obstacles(&mut voxelized_tile, nav_mesh_settings);
+    }
+    if span_filters.ledge_spans {
+        #[cfg(feature = "trace")]
+        let _span = info_span!("Filter ledge spans").entered();
+        filter_ledge_spans(&mut voxelized_tile, nav_mesh_settings);
+    }
+    if span_filters.low_height_spans {
+        #[cfg(feature = "trace")]
+        let _span = info_span!("Filter low height spans").entered();
+        filter_low_height_spans(&mut voxelized_tile, nav_mesh_settings);
+    }
+
     let mut cells = vec![OpenCell::default(); voxelized_tile.cells.len()];
     let mut span_count = 0;
 
@@ -423,7 +617,7 @@ pub fn build_open_heightfield_tile(
 
             if let Some(next_span) = iter.peek() {
                 // Need to check if space is large enough.
-                if next_span.min - span.max >= nav_mesh_settings.walkable_height {
+                if next_span.min - span.max >= u32::from(nav_mesh_settings.walkable_height) {
                     open_spans.push(OpenSpan {
                         min: span.max,
                         max: Some(next_span.min),
@@ -452,6 +646,7 @@ pub fn build_open_heightfield_tile(
         max_distance: 0,
         span_count,
         max_regions: 0,
+        input_hash: Some(voxelized_tile.input_hash),
     };
 
     // Assign tile_index & copy over areas.
@@ -475,6 +670,142 @@ pub fn build_open_heightfield_tile(
     open_tile
 }
 
+impl HeightSpan {
+    fn is_walkable(&self) -> bool {
+        self.traversable && self.area.is_some()
+    }
+}
+
+/// Makes non-walkable spans walkable if they're at most ``step_height`` above a walkable span directly below them. This lets agents step over curbs & the like whose tops aren't traversable on their own.
+fn filter_low_hanging_obstacles(
+    voxelized_tile: &mut VoxelizedTile,
+    nav_mesh_settings: &NavMeshSettings,
+) {
+    for cell in voxelized_tile.cells.iter_mut() {
+        let mut previous: Option<(bool, u32, Option<Area>)> = None;
+
+        for span in cell.spans.iter_mut() {
+            let walkable = span.is_walkable();
+
+            if let Some((true, previous_max, previous_area)) = previous {
+                if !walkable
+                    && span.max.abs_diff(previous_max) <= u32::from(nav_mesh_settings.step_height)
+                {
+                    span.traversable = true;
+                    span.area = previous_area;
+                }
+            }
+
+            // Use the original walkability so a stack of obstacles isn't made walkable one by one.
+            previous = Some((walkable, span.max, span.area));
+        }
+    }
+}
+
+/// Makes walkable spans next to a drop deeper than ``step_height``, or on a slope too steep to step across, unwalkable.
+fn filter_ledge_spans(voxelized_tile: &mut VoxelizedTile, nav_mesh_settings: &NavMeshSettings) {
+    let tile_side = nav_mesh_settings.get_tile_side_with_border();
+    let walkable_height = i64::from(nav_mesh_settings.walkable_height);
+    let step_height = i64::from(nav_mesh_settings.step_height);
+    let max_height = i64::from(u32::MAX);
+
+    // Floor & ceiling of the open space above the span at ``index`` in ``spans``.
+    let open_space = |spans: &[HeightSpan], index: usize| {
+        let floor = i64::from(spans[index].max);
+        let ceiling = spans
+            .get(index + 1)
+            .map_or(max_height, |span| i64::from(span.min));
+
+        (floor, ceiling)
+    };
+
+    let mut ledges = Vec::new();
+    for (i, cell) in voxelized_tile.cells.iter().enumerate() {
+        let row = i / tile_side;
+        let column = i % tile_side;
+
+        for (span_index, span) in cell.spans.iter().enumerate() {
+            if !span.is_walkable() {
+                continue;
+            }
+
+            let (floor, ceiling) = open_space(&cell.spans, span_index);
+
+            let mut lowest_drop = max_height;
+            let mut min_accessible = floor;
+            let mut max_accessible = floor;
+
+            for dir in 0..4 {
+                // Cells outside the tile are left to the neighbouring tile, the border is eroded anyway.
+                let neighbour_index = match dir {
+                    0 if column > 0 => i - 1,
+                    1 if row < tile_side - 1 => i + tile_side,
+                    2 if column < tile_side - 1 => i + 1,
+                    3 if row > 0 => i - tile_side,
+                    _ => continue,
+                };
+                let neighbour_spans = &voxelized_tile.cells[neighbour_index].spans;
+
+                // The open space below the neighbour's lowest span counts as a drop to the bottom of the world.
+                let neighbour_floor = -step_height;
+                let neighbour_ceiling = neighbour_spans
+                    .first()
+                    .map_or(max_height, |span| i64::from(span.min));
+                if ceiling.min(neighbour_ceiling) - floor.max(neighbour_floor) > walkable_height {
+                    lowest_drop = lowest_drop.min(neighbour_floor - floor);
+                }
+
+                for neighbour_span_index in 0..neighbour_spans.len() {
+                    let (neighbour_floor, neighbour_ceiling) =
+                        open_space(neighbour_spans, neighbour_span_index);
+
+                    // Skip neighbours we can't move into.
+                    if ceiling.min(neighbour_ceiling) - floor.max(neighbour_floor)
+                        <= walkable_height
+                    {
+                        continue;
+                    }
+
+                    lowest_drop = lowest_drop.min(neighbour_floor - floor);
+
+                    if (neighbour_floor - floor).abs() <= step_height {
+                        min_accessible = min_accessible.min(neighbour_floor);
+                        max_accessible = max_accessible.max(neighbour_floor);
+                    }
+                }
+            }
+
+            // A drop next to the span or a slope too steep to walk across both make it a ledge.
+            if lowest_drop < -step_height || max_accessible - min_accessible > step_height {
+                ledges.push((i, span_index));
+            }
+        }
+    }
+
+    for (cell_index, span_index) in ledges {
+        voxelized_tile.cells[cell_index].spans[span_index].area = None;
+    }
+}
+
+/// Makes walkable spans with less than ``walkable_height`` of open space above them unwalkable.
+fn filter_low_height_spans(
+    voxelized_tile: &mut VoxelizedTile,
+    nav_mesh_settings: &NavMeshSettings,
+) {
+    for cell in voxelized_tile.cells.iter_mut() {
+        for span_index in 0..cell.spans.len() {
+            let Some(next_min) = cell.spans.get(span_index + 1).map(|span| span.min) else {
+                continue;
+            };
+
+            let span = &mut cell.spans[span_index];
+            if next_min - span.max < u32::from(nav_mesh_settings.walkable_height) {
+                span.area = None;
+            }
+        }
+    }
+}
+
 fn link_neighbours(open_tile: &mut OpenTile, nav_mesh_settings: &NavMeshSettings) {
     let mut neighbour_spans = Vec::with_capacity(3);
 
@@ -520,12 +851,12 @@ fn link_neighbours(open_tile: &mut OpenTile, nav_mesh_settings: &NavMeshSettings
                 for (i, (min, max)) in neighbour_spans.iter().enumerate() {
                     if let Some((max, span_max)) = max.zip(span.max) {
                         let gap = span_max.min(max).abs_diff(span.min.max(*min));
-                        if gap < nav_mesh_settings.walkable_height {
+                        if gap < u32::from(nav_mesh_settings.walkable_height) {
                             continue;
                         }
                     }
 
-                    if min.abs_diff(span.min) < nav_mesh_settings.step_height {
+                    if min.abs_diff(span.min) < u32::from(nav_mesh_settings.step_height) {
                         span.neighbours[neighbour] = Some(i as u16);
                         break;
                     }
@@ -573,6 +904,50 @@ pub fn erode_walkable_area(open_tile: &mut OpenTile, nav_mesh_settings: &NavMesh
     }
 }
 
+/// Replaces the area of every walkable span with the median area of it & its 8 neighbours. Unwalkable spans are left alone & don't count towards the median.
+pub fn median_filter_areas(open_tile: &mut OpenTile, nav_mesh_settings: &NavMeshSettings) {
+    let tile_side = nav_mesh_settings.get_tile_side_with_border();
+    let mut areas = open_tile.areas.clone();
+
+    for (i, cell) in open_tile.cells.iter().enumerate() {
+        for span in cell.spans.iter() {
+            let Some(area) = open_tile.areas[span.tile_index] else {
+                continue;
+            };
+
+            // Missing & unwalkable neighbours count as the span's own area.
+            let mut neighbour_areas = [area; 9];
+            for dir in 0..4 {
+                let Some(index) = span.neighbours[dir] else {
+                    continue;
+                };
+                let other_cell_index = get_neighbour_index(tile_side, i, dir);
+                let other_span = &open_tile.cells[other_cell_index].spans[index as usize];
+                if let Some(other_area) = open_tile.areas[other_span.tile_index] {
+                    neighbour_areas[dir * 2] = other_area;
+                }
+
+                // Diagonal neighbour.
+                let diagonal_dir = (dir + 1) % 4;
+                let Some(index) = other_span.neighbours[diagonal_dir] else {
+                    continue;
+                };
+                let other_cell_index =
+                    get_neighbour_index(tile_side, other_cell_index, diagonal_dir);
+                let other_span = &open_tile.cells[other_cell_index].spans[index as usize];
+                if let Some(other_area) = open_tile.areas[other_span.tile_index] {
+                    neighbour_areas[dir * 2 + 1] = other_area;
+                }
+            }
+
+            neighbour_areas.sort_unstable();
+            areas[span.tile_index] = Some(neighbour_areas[4]);
+        }
+    }
+
+    open_tile.areas = areas;
+}
+
 pub fn calculate_distance_field(open_tile: &mut OpenTile, nav_mesh_settings: &NavMeshSettings) {
     let tile_side = nav_mesh_settings.get_tile_side_with_border();
     // Mark boundary cells.
@@ -756,3 +1131,385 @@ fn filter_tile(open_tile: &mut OpenTile, nav_mesh_settings: &NavMeshSettings) {
         }
     }
 }
+
+/// Marks every span whose floor is inside an obstacle as unwalkable.
+///
+/// This runs after [erode_walkable_area] so obstacles are expanded by ``walkable_radius`` here instead.
+pub(super) fn carve_obstacles(
+    open_tile: &mut OpenTile,
+    tile_coord: UVec2,
+    obstacles: &[ObstacleCollection],
+    nav_mesh_settings: &NavMeshSettings,
+) {
+    let tile_side = nav_mesh_settings.get_tile_side_with_border();
+    let tile_origin = nav_mesh_settings.get_tile_origin_with_border(tile_coord);
+    let expansion = nav_mesh_settings.get_border_size();
+    let step_height = f32::from(nav_mesh_settings.step_height) * nav_mesh_settings.cell_height;
+
+    for collection in obstacles.iter() {
+        let (min, max) = collection.obstacle.get_bounds(&collection.transform);
+
+        let min_cell = ((min.xz() - expansion - tile_origin) / nav_mesh_settings.cell_width)
+            .floor()
+            .as_ivec2()
+            .max(IVec2::ZERO);
+        let max_cell = ((max.xz() + expansion - tile_origin) / nav_mesh_settings.cell_width)
+            .floor()
+            .as_ivec2()
+            .min(IVec2::splat(tile_side as i32 - 1));
+        if min_cell.x > max_cell.x || min_cell.y > max_cell.y {
+            continue;
+        }
+
+        // Spans standing slightly below the obstacle (like a box resting on a step) are also covered.
+        let min_height = min.y - step_height;
+        let max_height = max.y;
+
+        for z in min_cell.y..=max_cell.y {
+            for x in min_cell.x..=max_cell.x {
+                let cell_center = tile_origin
+                    + (Vec2::new(x as f32, z as f32) + 0.5) * nav_mesh_settings.cell_width;
+                if !collection.obstacle.contains_point_xz(
+                    &collection.transform,
+                    cell_center,
+                    expansion,
+                ) {
+                    continue;
+                }
+
+                let cell = &open_tile.cells[x as usize + z as usize * tile_side];
+                for span in cell.spans.iter() {
+                    let floor = nav_mesh_settings.world_bottom_bound
+                        + span.min as f32 * nav_mesh_settings.cell_height;
+
+                    if floor >= min_height && floor <= max_height {
+                        open_tile.areas[span.tile_index] = None;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Relabels the walkable spans whose floor is inside any of the area volumes, like Recast's ``rcMarkConvexPolyArea``.
+pub(super) fn mark_area_volumes(
+    open_tile: &mut OpenTile,
+    tile_coord: UVec2,
+    area_volumes: &[AreaVolumeCollection],
+    nav_mesh_settings: &NavMeshSettings,
+) {
+    // Apply the highest areas last so they win where volumes overlap. Unwalkable wins over everything.
+    let mut area_volumes = area_volumes.iter().collect::<Vec<_>>();
+    area_volumes.sort_by_key(|collection| match collection.area_volume.area {
+        Some(area) => u32::from(area.0),
+        None => u32::MAX,
+    });
+
+    for collection in area_volumes {
+        for_each_span_area_in_volume(
+            open_tile,
+            tile_coord,
+            &collection.area_volume.shape,
+            &collection.transform,
+            nav_mesh_settings,
+            |area| {
+                if area.is_some() {
+                    *area = collection.area_volume.area;
+                }
+            },
+        );
+    }
+}
+
+/// Marks every span whose floor is inside any of the blockers as unwalkable.
+pub(super) fn mark_blockers(
+    open_tile: &mut OpenTile,
+    tile_coord: UVec2,
+    blockers: &[BlockerCollection],
+    nav_mesh_settings: &NavMeshSettings,
+) {
+    for collection in blockers.iter() {
+        for_each_span_area_in_volume(
+            open_tile,
+            tile_coord,
+            &collection.blocker.shape,
+            &collection.transform,
+            nav_mesh_settings,
+            |area| *area = None,
+        );
+    }
+}
+
+/// Calls ``callback`` with the area of every span whose floor is inside ``shape``.
+fn for_each_span_area_in_volume(
+    open_tile: &mut OpenTile,
+    tile_coord: UVec2,
+    shape: &NavMeshVolumeShape,
+    transform: &Transform,
+    nav_mesh_settings: &NavMeshSettings,
+    mut callback: impl FnMut(&mut Option<Area>),
+) {
+    let tile_side = nav_mesh_settings.get_tile_side_with_border();
+    let tile_origin = nav_mesh_settings.get_tile_origin_with_border(tile_coord);
+
+    let (min, max) = shape.get_bounds(transform);
+
+    let min_cell = ((min.xz() - tile_origin) / nav_mesh_settings.cell_width)
+        .floor()
+        .as_ivec2()
+        .max(IVec2::ZERO);
+    let max_cell = ((max.xz() - tile_origin) / nav_mesh_settings.cell_width)
+        .floor()
+        .as_ivec2()
+        .min(IVec2::splat(tile_side as i32 - 1));
+
+    for z in min_cell.y..=max_cell.y {
+        for x in min_cell.x..=max_cell.x {
+            let cell_center =
+                tile_origin + (Vec2::new(x as f32, z as f32) + 0.5) * nav_mesh_settings.cell_width;
+            if !shape.contains_point_xz(transform, cell_center) {
+                continue;
+            }
+
+            let cell = &open_tile.cells[x as usize + z as usize * tile_side];
+            for span in cell.spans.iter() {
+                let floor = nav_mesh_settings.world_bottom_bound
+                    + span.min as f32 * nav_mesh_settings.cell_height;
+
+                if floor >= min.y && floor <= max.y {
+                    callback(&mut open_tile.areas[span.tile_index]);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use smallvec::smallvec;
+
+    use super::*;
+    use crate::SpanFilters;
+
+    fn get_test_settings() -> NavMeshSettings {
+        NavMeshSettings::from_agent_and_bounds(0.5, 2.0, 50.0, -10.0)
+            .with_tile_width(8)
+            .with_walkable_radius(1)
+    }
+
+    fn walkable_span(min: u32, max: u32) -> HeightSpan {
+        HeightSpan {
+            min,
+            max,
+            traversable: true,
+            area: Some(Area(0)),
+        }
+    }
+
+    fn obstacle_span(min: u32, max: u32) -> HeightSpan {
+        HeightSpan {
+            min,
+            max,
+            traversable: false,
+            area: None,
+        }
+    }
+
+    /// Tile where every cell has a single walkable span with its top at ``height``.
+    fn flat_tile(nav_mesh_settings: &NavMeshSettings, height: u32) -> VoxelizedTile {
+        let cell = VoxelCell {
+            spans: smallvec![walkable_span(0, height)],
+        };
+
+        VoxelizedTile {
+            cells: vec![cell; nav_mesh_settings.get_tile_side_with_border().pow(2)]
+                .into_boxed_slice(),
+            input_hash: 0,
+        }
+    }
+
+    #[test]
+    fn test_triangle_hash_is_stable() {
+        let mut triangle = TileTriangle {
+            vertices: [
+                Vec3A::new(0.0, 1.0, 2.0),
+                Vec3A::new(3.5, 1.0, 2.0),
+                Vec3A::new(0.0, 1.25, 4.0),
+            ],
+            traversable: true,
+            area: Some(Area(1)),
+        };
+        // Baked tiles store these hashes, changing them invalidates every existing bake.
+        assert_eq!(hash_tile_triangle(&triangle), 0xc97278aefd98b6a8);
+
+        triangle.traversable = false;
+        triangle.area = None;
+        assert_eq!(hash_tile_triangle(&triangle), 0xf395121a78d84874);
+    }
+
+    #[test]
+    fn test_tile_hash_ignores_affector_order() {
+        let nav_mesh_settings = get_test_settings();
+        let tile_coord = nav_mesh_settings.get_tile_containing_position(Vec2::new(1.0, 1.0));
+
+        let triangle = |translation: Vec3| TriangleCollection {
+            transform: Transform::from_translation(translation),
+            triangles: Triangles::Triangle([
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(1.0, 0.0, 0.0),
+                Vec3::new(0.0, 0.0, 1.0),
+            ]),
+            area: Some(Area(0)),
+        };
+        let hash = |collections: &[TriangleCollection]| {
+            hash_tile_geometry(tile_coord, collections, &[], &nav_mesh_settings)
+        };
+
+        let first = Vec3::new(0.25, 0.0, 0.25);
+        let second = Vec3::new(0.75, 0.5, 0.5);
+        let forward = hash(&[triangle(first), triangle(second)]);
+        assert_eq!(forward, hash(&[triangle(second), triangle(first)]));
+        assert_ne!(forward, hash(&[triangle(first)]));
+        assert_ne!(
+            forward,
+            hash(&[triangle(first), triangle(second + Vec3::X * 0.5)])
+        );
+
+        // Triangles outside the tile don't change its hash.
+        assert_eq!(
+            hash(&[triangle(first)]),
+            hash(&[triangle(first), triangle(Vec3::new(20.0, 0.0, 20.0))])
+        );
+    }
+
+    #[test]
+    fn test_filter_ledge_spans() {
+        let nav_mesh_settings = get_test_settings();
+        let tile_side = nav_mesh_settings.get_tile_side_with_border();
+
+        let mut voxelized_tile = flat_tile(&nav_mesh_settings, 10);
+        for (i, cell) in voxelized_tile.cells.iter_mut().enumerate() {
+            let (row, column) = (i / tile_side, i % tile_side);
+            if column == 5 {
+                // Ridge a single cell wide.
+                cell.spans[0].max = 40;
+            } else if (row, column) == (5, 2) {
+                // Step low enough to walk onto.
+                cell.spans[0].max = 12;
+            }
+        }
+
+        filter_ledge_spans(&mut voxelized_tile, &nav_mesh_settings);
+
+        for (i, cell) in voxelized_tile.cells.iter().enumerate() {
+            let column = i % tile_side;
+            assert_eq!(
+                cell.spans[0].is_walkable(),
+                column != 5,
+                "Unexpected walkability at column {column}, row {}",
+                i / tile_side
+            );
+        }
+    }
+
+    #[test]
+    fn test_filter_low_hanging_obstacles() {
+        let nav_mesh_settings = get_test_settings();
+
+        let mut voxelized_tile = flat_tile(&nav_mesh_settings, 10);
+        // Curb, tall wall & a stack of curbs.
+        voxelized_tile.cells[0].spans.push(obstacle_span(11, 12));
+        voxelized_tile.cells[1].spans.push(obstacle_span(11, 20));
+        voxelized_tile.cells[2]
+            .spans
+            .extend([obstacle_span(11, 12), obstacle_span(13, 14)]);
+
+        filter_low_hanging_obstacles(&mut voxelized_tile, &nav_mesh_settings);
+
+        let walkable = |cell: usize| {
+            voxelized_tile.cells[cell]
+                .spans
+                .iter()
+                .map(HeightSpan::is_walkable)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(walkable(0), [true, true]);
+        assert_eq!(voxelized_tile.cells[0].spans[1].area, Some(Area(0)));
+        assert_eq!(walkable(1), [true, false]);
+        assert_eq!(walkable(2), [true, true, false]);
+    }
+
+    #[test]
+    fn test_filter_low_height_spans() {
+        let nav_mesh_settings = get_test_settings();
+        let walkable_height = u32::from(nav_mesh_settings.walkable_height);
+
+        let mut voxelized_tile = flat_tile(&nav_mesh_settings, 10);
+        voxelized_tile.cells[0]
+            .spans
+            .push(walkable_span(10 + walkable_height - 1, 40));
+        voxelized_tile.cells[1]
+            .spans
+            .push(walkable_span(10 + walkable_height, 40));
+
+        filter_low_height_spans(&mut voxelized_tile, &nav_mesh_settings);
+
+        assert!(!voxelized_tile.cells[0].spans[0].is_walkable());
+        assert!(voxelized_tile.cells[0].spans[1].is_walkable());
+        assert!(voxelized_tile.cells[1].spans[0].is_walkable());
+        assert!(voxelized_tile.cells[2].spans[0].is_walkable());
+    }
+
+    #[test]
+    fn test_span_filters_follow_settings() {
+        let mut nav_mesh_settings = get_test_settings();
+
+        let mut voxelized_tile = flat_tile(&nav_mesh_settings, 10);
+        voxelized_tile.cells[0].spans.push(obstacle_span(11, 12));
+
+        let curb_area = |nav_mesh_settings: &NavMeshSettings| {
+            let open_tile = build_open_heightfield_tile(voxelized_tile.clone(), nav_mesh_settings);
+            open_tile.areas[open_tile.cells[0].spans[0].tile_index]
+        };
+
+        // Without the filter the curb's top is open, but not walkable.
+        assert_eq!(curb_area(&nav_mesh_settings), None);
+
+        nav_mesh_settings.span_filters = SpanFilters {
+            low_hanging_obstacles: true,
+            ..default()
+        };
+        assert_eq!(curb_area(&nav_mesh_settings), Some(Area(0)));
+    }
+
+    #[test]
+    fn test_median_filter_areas() {
+        let nav_mesh_settings = get_test_settings();
+        let tile_side = nav_mesh_settings.get_tile_side_with_border();
+
+        let mut voxelized_tile = flat_tile(&nav_mesh_settings, 10);
+        for (i, cell) in voxelized_tile.cells.iter_mut().enumerate() {
+            let (row, column) = (i / tile_side, i % tile_side);
+            if column == 7 {
+                // Strip a single cell wide.
+                cell.spans[0].area = Some(Area(2));
+            } else if (1..=3).contains(&row) && (1..=3).contains(&column) {
+                cell.spans[0].area = Some(Area(3));
+            }
+        }
+        let mut open_tile = build_open_heightfield_tile(voxelized_tile, &nav_mesh_settings);
+
+        median_filter_areas(&mut open_tile, &nav_mesh_settings);
+
+        let area_at = |row: usize, column: usize| {
+            let span = &open_tile.cells[row * tile_side + column].spans[0];
+            open_tile.areas[span.tile_index]
+        };
+        // Ends of the strip are kept by the missing neighbours beyond the tile.
+        for row in 1..tile_side - 1 {
+            assert_eq!(area_at(row, 7), Some(Area(0)), "Strip kept at row {row}");
+        }
+        assert_eq!(area_at(2, 2), Some(Area(3)));
+        assert_eq!(area_at(1, 1), Some(Area(0)));
+    }
+}
@@ -45,38 +45,55 @@
 
 use std::marker::PhantomData;
 use std::num::NonZeroU16;
-use std::sync::{Arc, RwLock};
+use std::sync::{
+    atomic::{self, AtomicBool},
+    Arc, RwLock,
+};
+use std::time::Duration;
 
 use bevy::ecs::entity::EntityHashMap;
 use bevy::tasks::futures_lite::future;
 use bevy::tasks::{AsyncComputeTaskPool, Task};
 use bevy::{
-    ecs::system::Resource,
+    ecs::{
+        schedule::ScheduleLabel,
+        system::{Resource, SystemParam},
+    },
     prelude::*,
     utils::{HashMap, HashSet},
 };
 use colliders::OxidizedCollider;
 use contour::build_contours;
 use conversion::{
-    convert_geometry_collections, ColliderType, GeometryCollection, GeometryToConvert,
+    convert_geometry_collections, ColliderType, GeometryCollection, GeometryToConvert, Tessellation,
 };
 use heightfields::{
-    build_heightfield_tile, build_open_heightfield_tile, calculate_distance_field,
-    erode_walkable_area, HeightFieldCollection,
+    build_heightfield_tile, build_open_heightfield_tile, calculate_distance_field, carve_obstacles,
+    erode_walkable_area, hash_tile_geometry, mark_area_volumes, mark_blockers, median_filter_areas,
+    rasterize_into_heightfield_tile, AreaVolumeCollection, BlockerCollection,
+    HeightFieldCollection, ObstacleCollection, OpenTile, TriangleCollection, VoxelizedTile,
 };
 use mesher::build_poly_mesh;
+use off_mesh_links::build_jump_links;
 use crate::parry::parry3d::{math::Isometry, na::Vector3,shape::TypedShape};
-use regions::build_regions;
+use regions::{build_layer_regions, build_regions, build_regions_monotone};
 use smallvec::SmallVec;
-use tiles::{create_nav_mesh_tile_from_poly_mesh, NavMeshTile, NavMeshTiles};
+use tiles::{
+    create_nav_mesh_tile_from_poly_mesh, NavMeshTile, NavMeshTiles, DISABLED_POLYGON_FLAG,
+};
 
+pub mod agent;
+pub mod bake;
 pub mod colliders;
 mod contour;
 pub mod conversion;
+pub mod crowd;
 #[cfg(feature = "debug_draw")]
 pub mod debug_draw;
+pub mod export;
 mod heightfields;
 mod mesher;
+mod off_mesh_links;
 mod parry;
 pub mod query;
 mod regions;
@@ -93,8 +110,17 @@ pub enum OxidizedNavigation {
     Main,
 }
 
+/// Schedule containing the systems of [OxidizedNavigation::Main] when [OxidizedNavigationPlugin::with_bake_schedule] is used. Run from [Update] & by [bake::bake_nav_mesh].
+#[derive(ScheduleLabel, Debug, PartialEq, Eq, Hash, Clone)]
+struct NavMeshSchedule;
+
+fn run_nav_mesh_schedule(world: &mut World) {
+    world.run_schedule(NavMeshSchedule);
+}
+
 pub struct OxidizedNavigationPlugin<ColliderComponent> {
     pub settings: NavMeshSettings,
+    bake_schedule: bool,
     _collider_type: PhantomData<ColliderComponent>,
 }
 
@@ -106,9 +132,20 @@ where
     pub fn new(settings: NavMeshSettings) -> OxidizedNavigationPlugin<C> {
         OxidizedNavigationPlugin::<C> {
             settings,
+            bake_schedule: false,
             _collider_type: PhantomData::<C>,
         }
     }
+
+    /// Runs the systems of [OxidizedNavigation::Main] from their own schedule, required by [bake::bake_nav_mesh].
+    ///
+    /// **Note:** The schedule is run from an exclusive system in [Update], which can't run in parallel with any other system.
+    #[must_use]
+    pub fn with_bake_schedule(mut self) -> Self {
+        self.bake_schedule = true;
+
+        self
+    }
 }
 
 impl<C> Plugin for OxidizedNavigationPlugin<C>
@@ -123,30 +160,121 @@ where
             .init_resource::<NavMesh>()
             .init_resource::<GenerationTicker>()
             .init_resource::<NavMeshAffectorRelations>()
-            .init_resource::<ActiveGenerationTasks>();
+            .init_resource::<ActiveGenerationTasks>()
+            .init_resource::<TileObstacles>()
+            .init_resource::<NavMeshObstacleRelations>()
+            .init_resource::<TileAreaVolumes>()
+            .init_resource::<TileBlockers>()
+            .init_resource::<NavMeshBlockerRelations>()
+            .init_resource::<NavMeshAreaVolumeRelations>()
+            .init_resource::<DirtyObstacleTiles>()
+            .init_resource::<OpenTileCache>()
+            .init_resource::<StaticLayerCache>()
+            .init_resource::<StreamedTiles>()
+            .init_resource::<DebouncedTiles>()
+            .init_resource::<BakedTileCache>();
 
         app.add_systems(
             Update,
             handle_removed_affectors_system
                 .run_if(any_component_removed::<NavMeshAffector>())
-                .before(send_tile_rebuild_tasks_system::<C>)
+                .before(OxidizedNavigation::Main)
                 .in_set(OxidizedNavigation::RemovedComponent),
         );
 
+        let main_schedule = if self.bake_schedule {
+            app.init_schedule(NavMeshSchedule).add_systems(
+                Update,
+                run_nav_mesh_schedule.in_set(OxidizedNavigation::Main),
+            );
+
+            NavMeshSchedule.intern()
+        } else {
+            Update.intern()
+        };
+
         app.add_systems(
-            Update,
+            main_schedule,
             (
-                (remove_finished_tasks, update_navmesh_affectors_system::<C>),
+                (
+                    remove_finished_tasks,
+                    update_navmesh_affectors_system::<C>,
+                    update_nav_mesh_blockers_system,
+                    update_streamed_tiles_system,
+                ),
                 send_tile_rebuild_tasks_system::<C>.run_if(can_generate_new_tiles),
             )
                 .chain()
                 .in_set(OxidizedNavigation::Main),
         );
 
-        app.register_type::<NavMeshAffector>()
-            .register_type::<NavMeshAreaType>();
+        app.add_systems(
+            Update,
+            handle_removed_static_affectors_system
+                .run_if(any_component_removed::<NavMeshStaticAffector>())
+                .before(OxidizedNavigation::Main)
+                .in_set(OxidizedNavigation::RemovedComponent),
+        );
+
+        app.add_systems(
+            Update,
+            handle_removed_obstacles_system
+                .run_if(any_component_removed::<NavMeshObstacle>())
+                .before(OxidizedNavigation::Main)
+                .in_set(OxidizedNavigation::RemovedComponent),
+        );
+
+        app.add_systems(
+            Update,
+            handle_removed_blockers_system
+                .run_if(any_component_removed::<NavMeshBlocker>())
+                .before(OxidizedNavigation::Main)
+                .in_set(OxidizedNavigation::RemovedComponent),
+        );
+
+        app.add_systems(
+            Update,
+            handle_removed_area_volumes_system
+                .run_if(any_component_removed::<NavMeshAreaVolume>())
+                .before(OxidizedNavigation::Main)
+                .in_set(OxidizedNavigation::RemovedComponent),
+        );
+
+        app.add_systems(
+            main_schedule,
+            (
+                (
+                    update_nav_mesh_obstacles_system,
+                    update_nav_mesh_area_volumes_system,
+                ),
+                send_obstacle_rebuild_tasks_system.run_if(can_rebuild_obstacle_tiles),
+            )
+                .chain()
+                .after(update_navmesh_affectors_system::<C>)
+                .before(send_tile_rebuild_tasks_system::<C>)
+                .in_set(OxidizedNavigation::Main),
+        );
 
-        app.add_event::<TileGenerated>();
+        app.add_systems(
+            main_schedule,
+            update_nav_mesh_doors_system
+                .after(remove_finished_tasks)
+                .in_set(OxidizedNavigation::Main),
+        );
+
+        app.register_type::<NavMeshAffector>()
+            .register_type::<NavMeshStaticAffector>()
+            .register_type::<NavMeshPriorityAnchor>()
+            .register_type::<NavMeshStreamingAnchor>()
+            .register_type::<NavMeshAreaType>()
+            .register_type::<NavMeshTessellation>()
+            .register_type::<NavMeshDoor>()
+            .register_type::<NavMeshObstacle>()
+            .register_type::<NavMeshAreaVolume>()
+            .register_type::<NavMeshBlocker>();
+
+        app.add_event::<TileGenerated>()
+            .add_event::<OutOfWorldBounds>();
     }
 }
 
@@ -156,8 +284,15 @@ const MASK_CONTOUR_REGION: u32 = 0xffff; // Masks out the above value.
 #[derive(Resource, Default)]
 struct NavMeshAffectorRelations(EntityHashMap<SmallVec<[UVec2; 4]>>);
 
+struct GenerationTask {
+    tile_coord: UVec2,
+    /// Set when a newer task for the same tile has been queued.
+    cancelled: Arc<AtomicBool>,
+    task: Task<Option<UVec2>>,
+}
+
 #[derive(Resource, Default)]
-pub struct ActiveGenerationTasks(Vec<Task<Option<UVec2>>>);
+pub struct ActiveGenerationTasks(Vec<GenerationTask>);
 impl ActiveGenerationTasks {
     pub fn len(&self) -> usize {
         self.0.len()
@@ -165,6 +300,27 @@ impl ActiveGenerationTasks {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Cancels any in-flight tasks for ``tile_coord``, returning the cancellation flag for the task replacing them.
+    ///
+    /// Tasks that haven't started yet are dropped, running ones check the flag between generation steps.
+    fn supersede(&mut self, tile_coord: UVec2) -> Arc<AtomicBool> {
+        self.0.retain(|task| {
+            if task.tile_coord != tile_coord {
+                return true;
+            }
+
+            task.cancelled.store(true, atomic::Ordering::Relaxed);
+            false
+        });
+
+        Arc::default()
+    }
+
+    /// Returns true if a task for ``tile_coord`` hasn't finished yet.
+    fn contains(&self, tile_coord: UVec2) -> bool {
+        self.0.iter().any(|task| task.tile_coord == tile_coord)
+    }
 }
 
 /// Component for entities that should affect the nav-mesh.
@@ -172,6 +328,31 @@ impl ActiveGenerationTasks {
 #[reflect(Component)]
 pub struct NavMeshAffector;
 
+/// Marks a [NavMeshAffector] as static, for level geometry that rarely or never changes.
+///
+/// The static affectors of a tile are rasterized once & cached. Rebuilding the tile because a non-static affector changed then only rasterizes the non-static affectors on top of the cached layer. Changing a static affector discards the cached layer of every tile it touches.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct NavMeshStaticAffector;
+
+/// Marks an entity, like the player or an AI agent, whose surrounding tiles should be rebuilt first.
+///
+/// Dirty tiles are rebuilt in order of their distance to the closest anchor. Without any anchors or a [NavMeshTilePriority] resource tiles are rebuilt in arbitrary order.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct NavMeshPriorityAnchor;
+
+/// Marks an entity, like the player, around which the nav-mesh is generated when [NavMeshSettings::streaming] is enabled.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct NavMeshStreamingAnchor;
+
+/// Optional resource deciding the order dirty tiles are rebuilt in. Called with a tile's coordinate & the center of the tile on the XZ-plane, tiles with a lower value are rebuilt first.
+///
+/// Takes precedence over [NavMeshPriorityAnchor]s.
+#[derive(Resource)]
+pub struct NavMeshTilePriority(pub Box<dyn Fn(UVec2, Vec2) -> f32 + Send + Sync>);
+
 /// Optional component to define the area type of an entity. Setting this to ``None`` means that the entity isn't walkable.
 ///
 /// Any part of the nav-mesh generated from this entity will have this area type. Overlapping areas will prefer the higher area type.
@@ -179,9 +360,270 @@ pub struct NavMeshAffector;
 #[reflect(Component)]
 pub struct NavMeshAreaType(pub Option<Area>);
 
+/// Optional component overriding [NavMeshSettings::tessellation] for a curved [NavMeshAffector], like a large tower that needs a smoother outline or a pebble that needs next to none.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct NavMeshTessellation(pub Tessellation);
+
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct Area(pub u16);
 
+/// Component for doors, bridges, gates or anything else that should open & close parts of the nav-mesh at runtime without regenerating tiles.
+///
+/// While closed every polygon overlapping the door's box has ``closed_flags`` set, while open those flags are cleared. The box is centered on the entity's ``GlobalTransform``, rotated & scaled boxes use their axis-aligned bounds.
+///
+/// Flags are reapplied whenever a tile under the door is regenerated.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct NavMeshDoor {
+    /// Half extents of the door's box in local space.
+    pub half_extents: Vec3,
+    /// Whether the door lets agents through.
+    pub open: bool,
+    /// Flags set on overlapping polygons while the door is closed.
+    ///
+    /// Defaults to [DISABLED_POLYGON_FLAG] which is excluded by [tiles::PolygonFilter::default].
+    pub closed_flags: u16,
+}
+impl Default for NavMeshDoor {
+    fn default() -> Self {
+        Self {
+            half_extents: Vec3::splat(0.5),
+            open: true,
+            closed_flags: DISABLED_POLYGON_FLAG,
+        }
+    }
+}
+impl NavMeshDoor {
+    pub fn new(half_extents: Vec3, open: bool) -> Self {
+        Self {
+            half_extents,
+            open,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the world space minimum & maximum bound of the door.
+    pub fn get_bounds(&self, global_transform: &GlobalTransform) -> (Vec3, Vec3) {
+        let (scale, rotation, translation) = global_transform.to_scale_rotation_translation();
+        let rotation = Mat3::from_quat(rotation);
+
+        let half_extents = self.half_extents * scale;
+        let extents = rotation.x_axis.abs() * half_extents.x
+            + rotation.y_axis.abs() * half_extents.y
+            + rotation.z_axis.abs() * half_extents.z;
+
+        (translation - extents, translation + extents)
+    }
+}
+
+/// Component for lightweight temporary obstacles, like crates or parked vehicles.
+///
+/// Obstacles cut holes into the nav-mesh without contributing any geometry. When [NavMeshSettings::cache_open_tiles] is enabled changing an obstacle only reruns region, contour & mesh generation for the affected tiles instead of rebuilding them from their geometry.
+///
+/// Obstacles are positioned by the entity's ``GlobalTransform`` & scaled by its scale.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component)]
+pub enum NavMeshObstacle {
+    /// Upright cylinder with its base at the entity's position.
+    Cylinder { radius: f32, height: f32 },
+    /// Box centered on the entity's position. Only rotation around the Y-axis is taken into account.
+    Box { half_extents: Vec3 },
+}
+impl Default for NavMeshObstacle {
+    fn default() -> Self {
+        Self::Cylinder {
+            radius: 0.5,
+            height: 1.0,
+        }
+    }
+}
+impl NavMeshObstacle {
+    /// Returns the world space minimum & maximum bound of the obstacle.
+    pub fn get_bounds(&self, transform: &Transform) -> (Vec3, Vec3) {
+        match *self {
+            NavMeshObstacle::Cylinder { radius, height } => {
+                let radius = radius * transform.scale.x.max(transform.scale.z);
+                let height = height * transform.scale.y;
+
+                (
+                    transform.translation - Vec3::new(radius, 0.0, radius),
+                    transform.translation + Vec3::new(radius, height, radius),
+                )
+            }
+            NavMeshObstacle::Box { half_extents } => {
+                let half_extents = half_extents * transform.scale;
+                let (yaw, _, _) = transform.rotation.to_euler(EulerRot::YXZ);
+                let (sin, cos) = yaw.sin_cos();
+
+                let extents = Vec3::new(
+                    (cos * half_extents.x).abs() + (sin * half_extents.z).abs(),
+                    half_extents.y,
+                    (sin * half_extents.x).abs() + (cos * half_extents.z).abs(),
+                );
+
+                (
+                    transform.translation - extents,
+                    transform.translation + extents,
+                )
+            }
+        }
+    }
+
+    /// Returns true if ``point`` on the XZ-plane is within ``expansion`` of the obstacle's footprint.
+    pub fn contains_point_xz(&self, transform: &Transform, point: Vec2, expansion: f32) -> bool {
+        let offset = point - transform.translation.xz();
+
+        match *self {
+            NavMeshObstacle::Cylinder { radius, .. } => {
+                let radius = radius * transform.scale.x.max(transform.scale.z) + expansion;
+
+                offset.length_squared() <= radius * radius
+            }
+            NavMeshObstacle::Box { half_extents } => {
+                let half_extents = half_extents.xz() * transform.scale.xz() + expansion;
+                let (yaw, _, _) = transform.rotation.to_euler(EulerRot::YXZ);
+
+                // Rotate the offset into the box's local space.
+                let local = Vec2::from_angle(yaw).rotate(offset);
+
+                local.x.abs() <= half_extents.x && local.y.abs() <= half_extents.y
+            }
+        }
+    }
+}
+
+/// Component for marking part of the nav-mesh with an area without adding any geometry, like mud, roads or no-spawn zones.
+///
+/// Relabels the walkable parts of the nav-mesh whose floor is inside the volume, it can't make anything walkable. Setting ``area`` to ``None`` makes them unwalkable. Where volumes overlap the higher area is used, with ``None`` taking precedence over everything. Like [NavMeshObstacle]s, changing a volume only reruns region, contour & mesh generation for the affected tiles when [NavMeshSettings::cache_open_tiles] is enabled.
+///
+/// Volumes are positioned by the entity's ``GlobalTransform`` & scaled by its scale. Only rotation around the Y-axis is taken into account.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct NavMeshAreaVolume {
+    pub shape: NavMeshVolumeShape,
+    pub area: Option<Area>,
+}
+impl Default for NavMeshAreaVolume {
+    fn default() -> Self {
+        Self {
+            shape: NavMeshVolumeShape::Box {
+                half_extents: Vec3::splat(0.5),
+            },
+            area: None,
+        }
+    }
+}
+
+/// Shape of a [NavMeshAreaVolume] or [NavMeshBlocker].
+#[derive(Reflect, Clone, Debug)]
+pub enum NavMeshVolumeShape {
+    /// Box centered on the entity's position.
+    Box { half_extents: Vec3 },
+    /// Convex polygon on the XZ-plane extruded from ``min_height`` to ``max_height``. Points & heights are relative to the entity's position.
+    ConvexPrism {
+        points: Vec<Vec2>,
+        min_height: f32,
+        max_height: f32,
+    },
+}
+impl NavMeshVolumeShape {
+    /// Returns the world space minimum & maximum bound of the shape.
+    pub fn get_bounds(&self, transform: &Transform) -> (Vec3, Vec3) {
+        match self {
+            NavMeshVolumeShape::Box { half_extents } => NavMeshObstacle::Box {
+                half_extents: *half_extents,
+            }
+            .get_bounds(transform),
+            NavMeshVolumeShape::ConvexPrism {
+                points,
+                min_height,
+                max_height,
+            } => {
+                let (yaw, _, _) = transform.rotation.to_euler(EulerRot::YXZ);
+                let rotation = Vec2::from_angle(-yaw);
+
+                let (min, max) = points.iter().fold(
+                    (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+                    |(min, max), point| {
+                        let point = rotation.rotate(*point * transform.scale.xz());
+
+                        (min.min(point), max.max(point))
+                    },
+                );
+
+                let translation = transform.translation;
+                (
+                    Vec3::new(
+                        translation.x + min.x,
+                        translation.y + min_height * transform.scale.y,
+                        translation.z + min.y,
+                    ),
+                    Vec3::new(
+                        translation.x + max.x,
+                        translation.y + max_height * transform.scale.y,
+                        translation.z + max.y,
+                    ),
+                )
+            }
+        }
+    }
+
+    /// Returns true if ``point`` on the XZ-plane is within the shape's footprint.
+    pub fn contains_point_xz(&self, transform: &Transform, point: Vec2) -> bool {
+        match self {
+            NavMeshVolumeShape::Box { half_extents } => NavMeshObstacle::Box {
+                half_extents: *half_extents,
+            }
+            .contains_point_xz(transform, point, 0.0),
+            NavMeshVolumeShape::ConvexPrism { points, .. } => {
+                let (yaw, _, _) = transform.rotation.to_euler(EulerRot::YXZ);
+
+                // Move the point into the prism's local space.
+                let local = Vec2::from_angle(yaw).rotate(point - transform.translation.xz())
+                    / transform.scale.xz();
+
+                // Inside if the point is on the same side of every edge, regardless of winding.
+                let mut side = 0.0;
+                for (i, a) in points.iter().enumerate() {
+                    let b = points[(i + 1) % points.len()];
+                    let cross = (b - *a).perp_dot(local - *a);
+                    if cross == 0.0 {
+                        continue;
+                    }
+                    if cross * side < 0.0 {
+                        return false;
+                    }
+                    side = cross;
+                }
+
+                points.len() >= 3
+            }
+        }
+    }
+}
+
+/// Component for invisible "keep out" volumes, like stage boundaries or walls only AI should respect.
+///
+/// Every span with its floor inside the volume is made unwalkable before the walkable area is eroded, so the nav-mesh is pulled back from the blocker just like from a wall. Unlike a collider with [NavMeshAffector] it doesn't add any walkable surface on top. Changing a blocker rebuilds the tiles it overlaps from their geometry.
+///
+/// Blockers are positioned by the entity's ``GlobalTransform`` & scaled by its scale. Only rotation around the Y-axis is taken into account.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct NavMeshBlocker {
+    pub shape: NavMeshVolumeShape,
+}
+impl Default for NavMeshBlocker {
+    fn default() -> Self {
+        Self {
+            shape: NavMeshVolumeShape::Box {
+                half_extents: Vec3::splat(0.5),
+            },
+        }
+    }
+}
+
 /*
 *   Neighbours:
 *   0: (-1, 0),
@@ -203,108 +645,629 @@ struct TileAffectors(HashMap<UVec2, HashSet<Entity>>);
 #[derive(Default, Resource)]
 struct DirtyTiles(HashSet<UVec2>);
 
-/// Settings for nav-mesh generation.
-#[derive(Resource, Clone)]
-pub struct NavMeshSettings {
-    /// The horizontal resolution of the voxelized tile.
-    ///
-    /// **Suggested value**: 1/2 of character radius.
-    ///
-    /// Smaller values will increase tile generation times with diminishing returns in nav-mesh detail.
-    pub cell_width: f32,
-    /// The vertical resolution of the voxelized tile.
-    ///
-    /// **Suggested value**: 1/2 of cell_width.
-    ///
-    /// Smaller values will increase tile generation times with diminishing returns in nav-mesh detail.
-    pub cell_height: f32,
+#[derive(Default, Resource, Deref, DerefMut)]
+struct TileObstacles(HashMap<UVec2, HashSet<Entity>>);
 
-    /// Length of a tile's side in cells. Resulting size in world units is ``tile_width * cell_width``.
-    ///
-    /// **Suggested value**: ???
-    ///
-    /// Higher means more to update each time something within the tile changes, smaller means you will have more overhead from connecting the edges to other tiles & generating the tile itself.
-    pub tile_width: u16,
+#[derive(Resource, Default)]
+struct NavMeshObstacleRelations(EntityHashMap<SmallVec<[UVec2; 4]>>);
 
-    /// Extents of the world as measured from the world origin (0.0, 0.0) on the XZ-plane.
-    ///
-    /// **Suggested value**: As small as possible whilst still keeping the entire world within it.
-    ///
-    /// This exists because figuring out which tile we are in around the world origin would not work without it.
-    pub world_half_extents: f32,
-    /// Bottom extents of the world on the Y-axis. The top extents is capped by ``world_bottom_bound + cell_height * u16::MAX``.
-    ///
-    /// **Suggested value**: Minium Y position of anything in the world that should be covered by the nav mesh.
-    pub world_bottom_bound: f32,
+#[derive(Default, Resource, Deref, DerefMut)]
+struct TileAreaVolumes(HashMap<UVec2, HashSet<Entity>>);
 
-    /// Maximum incline/slope traversable when navigating in radians.
-    pub max_traversable_slope_radians: f32,
-    /// Minimum open height for an area to be considered walkable in cell_height(s).
-    ///
-    /// **Suggested value**: The height of character * ``cell_height``, rounded up.
-    pub walkable_height: u16,
-    /// This will "pull-back" the nav-mesh from edges, meaning anywhere on the nav-mesh will be walkable for a character with a radius of ``walkable_radius * cell_width``.
-    ///
-    /// **Suggested value**: ``ceil(character_radius / cell_width)`` (2-3 if `cell_width` is 1/2 of ``character_radius``)  
-    pub walkable_radius: u16,
-    /// Maximum height difference that is still considered traversable in cell_height(s). This smooths out stair steps and small ledges.
-    pub step_height: u16,
+#[derive(Default, Resource, Deref, DerefMut)]
+struct TileBlockers(HashMap<UVec2, HashSet<Entity>>);
 
-    /// Minimum size of a region in cells, anything smaller than this will be removed. This is used to filter out smaller disconnected island that may appear on surfaces like tables.
-    pub min_region_area: u32,
-    /// Maximum size of a region in cells we can merge other regions into.a
-    pub max_region_area_to_merge_into: u32,
+#[derive(Resource, Default)]
+struct NavMeshBlockerRelations(EntityHashMap<SmallVec<[UVec2; 4]>>);
 
-    /// Maximum length of an edge before it's split.
-    ///
-    /// **Suggested value**: Start high and reduce if there are issues.
-    pub max_edge_length: u16,
-    /// Maximum difference allowed for simplified contour generation on the XZ-plane in cell_width(s).
-    ///
-    /// **Suggested value range**: `[1.1, 1.5]`
-    pub max_contour_simplification_error: f32,
+#[derive(Resource, Default)]
+struct NavMeshAreaVolumeRelations(EntityHashMap<SmallVec<[UVec2; 4]>>);
 
-    /// Max tiles to generate in parallel at once. A value of ``None`` will result in no limit.
-    ///
-    /// Adjust this to control memory & CPU usage. More tiles generating at once will have a higher memory footprint.
-    pub max_tile_generation_tasks: Option<NonZeroU16>,
+/// Set of tiles whose obstacles or area volumes have changed & can be rebuilt from [OpenTileCache].
+#[derive(Default, Resource)]
+struct DirtyObstacleTiles(HashSet<UVec2>);
+
+/// Returns ``entities`` in the order they should be gathered in. Sorted by their [GlobalTransform] when [NavMeshSettings::deterministic] is set, otherwise in [HashSet] order.
+fn ordered_entities(
+    entities: &HashSet<Entity>,
+    deterministic: bool,
+    get_transform: impl Fn(Entity) -> Option<GlobalTransform>,
+) -> Vec<Entity> {
+    let mut entities = entities.iter().copied().collect::<Vec<_>>();
+    if deterministic {
+        // Entity ids depend on spawn & despawn history, they only break ties between entities sharing a transform.
+        entities.sort_by_cached_key(|entity| {
+            let transform = get_transform(*entity).map(|global_transform| {
+                global_transform.affine().to_cols_array().map(f32::to_bits)
+            });
+            (transform, *entity)
+        });
+    }
+
+    entities
 }
-impl NavMeshSettings {
-    /// Helper function for creating nav-mesh settings with reasonable defaults from the size of your navigation agent and bounds of your world.
-    ///
-    ///
-    #[inline]
-    pub fn from_agent_and_bounds(
-        agent_radius: f32,
-        agent_height: f32,
-        world_half_extents: f32,
-        world_bottom_bound: f32,
-    ) -> Self {
-        let cell_width = agent_radius / 2.0;
-        let cell_height = agent_radius / 4.0;
 
-        let walkable_height = (agent_height / cell_height) as u16;
+/// Orders dirty tiles by [NavMeshTilePriority] or distance to the closest [NavMeshPriorityAnchor].
+#[derive(SystemParam)]
+struct TilePrioritizer<'w, 's> {
+    tile_priority: Option<Res<'w, NavMeshTilePriority>>,
+    anchor_query: Query<'w, 's, &'static GlobalTransform, With<NavMeshPriorityAnchor>>,
+}
+impl TilePrioritizer<'_, '_> {
+    /// Moves the ``count`` tiles with the highest priority to the front of ``tiles`` in order. Leaves ``tiles`` as is when there's nothing to prioritize by.
+    fn prioritize(&self, tiles: &mut [UVec2], count: usize, nav_mesh_settings: &NavMeshSettings) {
+        let anchors = self
+            .anchor_query
+            .iter()
+            .map(|global_transform| global_transform.translation().xz())
+            .collect::<SmallVec<[Vec2; 4]>>();
+        if tiles.is_empty() || (self.tile_priority.is_none() && anchors.is_empty()) {
+            return;
+        }
 
-        Self {
-            cell_width,
-            cell_height,
-            tile_width: 120,
-            world_half_extents: world_half_extents.abs(),
-            world_bottom_bound,
-            max_traversable_slope_radians: 50.0_f32.to_radians(),
-            walkable_height,
-            walkable_radius: 2,
-            step_height: 3,
-            min_region_area: 100,
-            max_region_area_to_merge_into: 500,
-            max_edge_length: 80,
-            max_contour_simplification_error: 1.1,
-            max_tile_generation_tasks: NonZeroU16::new(8),
+        let half_tile_size = nav_mesh_settings.get_tile_size() / 2.0;
+        let mut prioritized = tiles
+            .iter()
+            .map(|tile_coord| {
+                let center = nav_mesh_settings.get_tile_origin(*tile_coord) + half_tile_size;
+                let priority = match &self.tile_priority {
+                    Some(tile_priority) => (tile_priority.0)(*tile_coord, center),
+                    None => anchors
+                        .iter()
+                        .map(|anchor| anchor.distance_squared(center))
+                        .fold(f32::INFINITY, f32::min),
+                };
+
+                (priority, *tile_coord)
+            })
+            .collect::<Vec<_>>();
+
+        let compare = |a: &(f32, UVec2), b: &(f32, UVec2)| a.0.total_cmp(&b.0);
+        let count = count.min(prioritized.len());
+        if count < prioritized.len() {
+            prioritized.select_nth_unstable_by(count, compare);
         }
-    }
-    /// Setter for [`NavMeshSettings::walkable_radius`]
-    pub fn with_walkable_radius(mut self, walkable_radius: u16) -> Self {
-        self.walkable_radius = walkable_radius;
+        prioritized[..count].sort_unstable_by(compare);
+
+        for (tile_coord, (_, prioritized)) in tiles.iter_mut().zip(prioritized) {
+            *tile_coord = prioritized;
+        }
+    }
+}
+
+/// Holds back dirty tiles that are outside the streaming range & not loaded, or whose affectors haven't settled yet.
+#[derive(SystemParam)]
+struct TileScheduling<'w> {
+    time: Res<'w, Time>,
+    streamed_tiles: ResMut<'w, StreamedTiles>,
+    debounced_tiles: ResMut<'w, DebouncedTiles>,
+}
+impl TileScheduling<'_> {
+    fn is_ready(&self, tile_coord: UVec2, nav_mesh_settings: &NavMeshSettings) -> bool {
+        // Loaded tiles are kept up to date until they're unloaded.
+        if nav_mesh_settings.streaming.is_some()
+            && !self.streamed_tiles.in_range.contains(&tile_coord)
+            && !self.streamed_tiles.loaded.contains(&tile_coord)
+        {
+            return false;
+        }
+
+        match (
+            nav_mesh_settings.affector_debounce,
+            self.debounced_tiles.0.get(&tile_coord),
+        ) {
+            (Some(debounce), Some(changed)) => {
+                self.time.elapsed().saturating_sub(*changed) >= debounce
+            }
+            _ => true,
+        }
+    }
+
+    fn mark_sent(&mut self, tile_coord: UVec2, nav_mesh_settings: &NavMeshSettings) {
+        if nav_mesh_settings.streaming.is_some() {
+            self.streamed_tiles.loaded.insert(tile_coord);
+        }
+        self.debounced_tiles.0.remove(&tile_coord);
+    }
+}
+
+/// Obstacles, area volumes & blockers of every tile.
+///
+/// Blockers are applied before the open tile is eroded, obstacles & area volumes after, to what [OpenTileCache] stores.
+#[derive(SystemParam)]
+struct TileModifiers<'w, 's> {
+    tile_obstacles: Res<'w, TileObstacles>,
+    tile_area_volumes: Res<'w, TileAreaVolumes>,
+    tile_blockers: Res<'w, TileBlockers>,
+    obstacle_query: Query<'w, 's, (&'static NavMeshObstacle, &'static GlobalTransform)>,
+    area_volume_query: Query<'w, 's, (&'static NavMeshAreaVolume, &'static GlobalTransform)>,
+    blocker_query: Query<'w, 's, (&'static NavMeshBlocker, &'static GlobalTransform)>,
+}
+impl TileModifiers<'_, '_> {
+    fn gather_blockers(&self, tile_coord: UVec2, deterministic: bool) -> Vec<BlockerCollection> {
+        let Some(blockers) = self.tile_blockers.get(&tile_coord) else {
+            return Vec::new();
+        };
+
+        self.blocker_query
+            .iter_many(ordered_entities(blockers, deterministic, |entity| {
+                self.blocker_query
+                    .get(entity)
+                    .ok()
+                    .map(|(_, global_transform)| *global_transform)
+            }))
+            .map(|(blocker, global_transform)| BlockerCollection {
+                transform: global_transform.compute_transform(),
+                blocker: blocker.clone(),
+            })
+            .collect()
+    }
+
+    fn gather(&self, tile_coord: UVec2, deterministic: bool) -> OpenTileModifiers {
+        let obstacles = self
+            .tile_obstacles
+            .get(&tile_coord)
+            .map(|obstacles| {
+                self.obstacle_query
+                    .iter_many(ordered_entities(obstacles, deterministic, |entity| {
+                        self.obstacle_query
+                            .get(entity)
+                            .ok()
+                            .map(|(_, global_transform)| *global_transform)
+                    }))
+                    .map(|(obstacle, global_transform)| ObstacleCollection {
+                        transform: global_transform.compute_transform(),
+                        obstacle: *obstacle,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let area_volumes = self
+            .tile_area_volumes
+            .get(&tile_coord)
+            .map(|area_volumes| {
+                self.area_volume_query
+                    .iter_many(ordered_entities(area_volumes, deterministic, |entity| {
+                        self.area_volume_query
+                            .get(entity)
+                            .ok()
+                            .map(|(_, global_transform)| *global_transform)
+                    }))
+                    .map(|(area_volume, global_transform)| AreaVolumeCollection {
+                        transform: global_transform.compute_transform(),
+                        area_volume: area_volume.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        OpenTileModifiers {
+            obstacles,
+            area_volumes,
+        }
+    }
+}
+
+/// Affectors & modifiers gathered for tile generation tasks.
+#[allow(clippy::type_complexity)]
+#[derive(SystemParam)]
+struct TileInputs<'w, 's, C: OxidizedCollider> {
+    tile_affectors: Res<'w, TileAffectors>,
+    tile_modifiers: TileModifiers<'w, 's>,
+    collider_query: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static C,
+            &'static GlobalTransform,
+            Option<&'static NavMeshAreaType>,
+            Option<&'static NavMeshTessellation>,
+            Has<NavMeshStaticAffector>,
+        ),
+        With<NavMeshAffector>,
+    >,
+    /// Heightfields shared between the tiles generated in the same frame.
+    heightfields: Local<'s, EntityHashMap<Arc<HeightFieldCollection>>>,
+}
+
+/// Dirty tiles & what decides which of them are generated next.
+#[derive(SystemParam)]
+struct TileQueue<'w, 's> {
+    dirty_tiles: ResMut<'w, DirtyTiles>,
+    tile_prioritizer: TilePrioritizer<'w, 's>,
+    tile_scheduling: TileScheduling<'w>,
+    tiles_to_generate: Local<'s, Vec<UVec2>>,
+}
+impl TileQueue<'_, '_> {
+    /// Takes up to ``count`` dirty tiles that are ready to be generated, highest priority first.
+    fn take_ready(
+        &mut self,
+        count: usize,
+        nav_mesh_settings: &NavMeshSettings,
+    ) -> std::vec::Drain<'_, UVec2> {
+        let Self {
+            dirty_tiles,
+            tile_prioritizer,
+            tile_scheduling,
+            tiles_to_generate,
+        } = self;
+
+        tiles_to_generate.extend(
+            dirty_tiles
+                .0
+                .iter()
+                .filter(|tile_coord| tile_scheduling.is_ready(**tile_coord, nav_mesh_settings)),
+        );
+        tile_prioritizer.prioritize(tiles_to_generate, count, nav_mesh_settings);
+        tiles_to_generate.truncate(count);
+
+        for tile_coord in tiles_to_generate.iter() {
+            dirty_tiles.0.remove(tile_coord);
+            tile_scheduling.mark_sent(*tile_coord, nav_mesh_settings);
+        }
+
+        tiles_to_generate.drain(..)
+    }
+}
+
+/// Caches filled by tile generation.
+#[derive(SystemParam)]
+struct TileCaches<'w> {
+    open_tile_cache: ResMut<'w, OpenTileCache>,
+    static_layer_cache: Res<'w, StaticLayerCache>,
+    baked_tile_cache: Res<'w, BakedTileCache>,
+}
+impl TileCaches<'_> {
+    /// Spawns a task removing ``tile_coord`` from the nav-mesh & every cache.
+    fn remove_tile(&mut self, generation: u64, tile_coord: UVec2, nav_mesh: &NavMesh) {
+        self.open_tile_cache
+            .requested_generations
+            .remove(&tile_coord);
+
+        AsyncComputeTaskPool::get()
+            .spawn(remove_tile(
+                generation,
+                tile_coord,
+                nav_mesh.0.clone(),
+                self.open_tile_cache.tiles.clone(),
+                self.static_layer_cache.layers.clone(),
+                self.baked_tile_cache.tiles.clone(),
+            ))
+            .detach();
+    }
+}
+
+/// Obstacles & area volumes overlapping a tile.
+#[derive(Default)]
+struct OpenTileModifiers {
+    obstacles: Vec<ObstacleCollection>,
+    area_volumes: Vec<AreaVolumeCollection>,
+}
+impl OpenTileModifiers {
+    fn is_empty(&self) -> bool {
+        self.obstacles.is_empty() && self.area_volumes.is_empty()
+    }
+}
+
+/// Cached open tiles & the generation they were built for.
+type CachedOpenTiles = Arc<RwLock<HashMap<UVec2, (u64, Arc<OpenTile>)>>>;
+
+/// Eroded open heightfields of generated tiles, only filled when [NavMeshSettings::cache_open_tiles] is enabled.
+#[derive(Default, Resource)]
+struct OpenTileCache {
+    tiles: CachedOpenTiles,
+    /// Generation of the latest full rebuild started for each tile. The cache can only be used once it has caught up to this.
+    requested_generations: HashMap<UVec2, u64>,
+}
+
+/// When an affector last changed in each tile, used by [NavMeshSettings::affector_debounce].
+#[derive(Default, Resource)]
+struct DebouncedTiles(HashMap<UVec2, Duration>);
+
+/// How many times the geometry of a tile loaded by [bake::load_baked_nav_mesh] may fail to match its hash before the baked tile is discarded.
+const MAX_BAKED_TILE_MISMATCHES: u8 = 1;
+
+/// A tile loaded by [bake::load_baked_nav_mesh].
+struct LoadedBakedTile {
+    /// Generation the tile was added to the nav-mesh with.
+    generation: u64,
+    input_hash: u64,
+    /// Mismatches left before the baked tile is discarded.
+    mismatches_left: u8,
+    tile: NavMeshTile,
+}
+
+/// Baked tiles that haven't been matched against their affectors yet.
+type CachedBakedTiles = Arc<RwLock<HashMap<UVec2, LoadedBakedTile>>>;
+
+/// Tiles loaded by [bake::load_baked_nav_mesh]. The first time one of them would be generated its geometry is hashed instead & if it matches the baked tile the generation is skipped.
+///
+/// A tile stays until its geometry has matched once, it's removed or its geometry didn't match more than [MAX_BAKED_TILE_MISMATCHES] times. If the geometry doesn't match at first, for example because affectors spawned this frame haven't had their ``GlobalTransform`` updated yet, the baked tile is put back once it does.
+#[derive(Default, Resource)]
+struct BakedTileCache {
+    tiles: CachedBakedTiles,
+}
+
+/// Tiles loaded by [NavMeshSettings::streaming].
+#[derive(Default, Resource)]
+struct StreamedTiles {
+    /// Tiles within ``load_radius`` of an anchor.
+    in_range: HashSet<UVec2>,
+    /// Tiles sent for generation that haven't been unloaded since.
+    loaded: HashSet<UVec2>,
+}
+
+/// Cached static layers & the version they were built for.
+type CachedStaticLayers = Arc<RwLock<HashMap<UVec2, (u64, Arc<VoxelizedTile>)>>>;
+
+/// Voxelized static affectors of each tile. See [NavMeshStaticAffector].
+#[derive(Default, Resource)]
+struct StaticLayerCache {
+    layers: CachedStaticLayers,
+    /// Bumped whenever a static affector in the tile changes. A cached layer is only valid for the version it was built for.
+    versions: HashMap<UVec2, u64>,
+}
+impl StaticLayerCache {
+    fn invalidate(&mut self, tile_coord: UVec2) {
+        *self.versions.entry(tile_coord).or_default() += 1;
+    }
+
+    fn version(&self, tile_coord: UVec2) -> u64 {
+        self.versions.get(&tile_coord).copied().unwrap_or(0)
+    }
+
+    fn get_valid_layer(&self, tile_coord: UVec2) -> Option<Arc<VoxelizedTile>> {
+        let version = self.version(tile_coord);
+        let layers = self.layers.read().ok()?;
+
+        layers
+            .get(&tile_coord)
+            .filter(|(cached_version, _)| *cached_version == version)
+            .map(|(_, layer)| layer.clone())
+    }
+}
+
+/// How the static affectors of a tile should be rasterized in [build_tile].
+enum StaticLayer {
+    /// The tile has no static affectors.
+    None,
+    Cached(Arc<VoxelizedTile>),
+    /// Rasterize the static affectors & store them in the cache.
+    Build {
+        version: u64,
+        geometry_collections: Vec<GeometryCollection>,
+        heightfields: Box<[Arc<HeightFieldCollection>]>,
+        cache: CachedStaticLayers,
+    },
+}
+
+/// Settings for generating jump-down & jump-across [tiles::OffMeshLink]s along the walls of the nav-mesh.
+///
+/// **Note:** Links are only generated between polygons in the same tile, neighbouring tiles aren't scanned. A ledge or gap whose landing lies in another tile gets no link,
+/// so levels relying on jumps should use a [NavMeshSettings::tile_width] large enough to keep the jumps within single tiles.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct JumpLinkSettings {
+    /// Maximum height in cell_height(s) an agent can drop down from a ledge.
+    pub max_drop_height: u16,
+    /// Maximum width in cell_width(s) of a gap an agent can jump across.
+    pub max_jump_distance: u16,
+    /// Drops no higher than this in cell_height(s) can also be climbed back up, making their links two-way.
+    pub max_climb_height: u16,
+    /// Area given to jump-down links.
+    pub jump_down_area: Area,
+    /// Area given to jump-across links.
+    pub jump_across_area: Area,
+}
+
+/// Optional filters run on the voxelized tile before the walkable area is built, like the ones in Recast.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpanFilters {
+    /// Makes obstacles at most ``step_height`` tall standing on walkable ground walkable, like curbs & stair steps.
+    pub low_hanging_obstacles: bool,
+    /// Removes walkable spans next to a drop deeper than ``step_height``. Keeps the nav-mesh off thin railings & pulls it back from cliff edges.
+    pub ledge_spans: bool,
+    /// Marks spans with less than ``walkable_height`` of clearance as unwalkable instead of dropping them, so they're treated as walls by the other filters & erosion.
+    pub low_height_spans: bool,
+}
+impl SpanFilters {
+    /// All filters enabled.
+    pub const ALL: Self = Self {
+        low_hanging_obstacles: true,
+        ledge_spans: true,
+        low_height_spans: true,
+    };
+}
+
+/// Algorithm used to partition the walkable area of a tile into regions before contours & polygons are built.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum RegionPartitioning {
+    /// Floods regions outward from the center of walkable areas using a distance field. The slowest option but produces the nicest regions.
+    #[default]
+    Watershed,
+    /// Sweeps the tile row by row. The fastest option, suitable for tiles rebuilt often like when streaming, but produces long thin polygons.
+    Monotone,
+    /// Sweeps the tile like [RegionPartitioning::Monotone] & merges the result into non-overlapping layers. Faster than [RegionPartitioning::Watershed] & handles large open areas well.
+    Layers,
+}
+
+/// Settings for only generating the nav-mesh around [NavMeshStreamingAnchor]s.
+///
+/// Tiles outside the load radius of every anchor are still tracked & marked dirty when their affectors change, they're generated once an anchor comes close enough. Tiles that have been loaded are regenerated as usual until they're unloaded.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreamingSettings {
+    /// Tiles within this distance of an anchor on the XZ-plane are generated.
+    pub load_radius: f32,
+    /// Generated tiles further than this from every anchor on the XZ-plane are removed.
+    ///
+    /// **Suggested value**: Larger than ``load_radius`` so tiles at the edge aren't loaded & unloaded repeatedly.
+    pub unload_radius: f32,
+}
+
+/// Settings for nav-mesh generation.
+#[derive(Resource, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct NavMeshSettings {
+    /// The horizontal resolution of the voxelized tile.
+    ///
+    /// **Suggested value**: 1/2 of character radius.
+    ///
+    /// Smaller values will increase tile generation times with diminishing returns in nav-mesh detail.
+    pub cell_width: f32,
+    /// The vertical resolution of the voxelized tile.
+    ///
+    /// **Suggested value**: 1/2 of cell_width.
+    ///
+    /// Smaller values will increase tile generation times with diminishing returns in nav-mesh detail.
+    pub cell_height: f32,
+
+    /// Length of a tile's side in cells. Resulting size in world units is ``tile_width * cell_width``.
+    ///
+    /// **Suggested value**: ???
+    ///
+    /// Higher means more to update each time something within the tile changes, smaller means you will have more overhead from connecting the edges to other tiles & generating the tile itself.
+    pub tile_width: u16,
+
+    /// Bounds of the world on the XZ-plane. Tile ``(0, 0)`` starts at ``world_bounds.min``.
+    ///
+    /// **Suggested value**: As small as possible whilst still keeping the entire world within it.
+    ///
+    /// Anything outside the bounds isn't added to the nav-mesh & is reported with [OutOfWorldBounds]. At most ``65536`` tiles fit along each axis, see [tiles::PolyRef].
+    pub world_bounds: Rect,
+    /// Bottom extents of the world on the Y-axis. The top extents is capped by ``world_bottom_bound + cell_height * u32::MAX``.
+    ///
+    /// **Suggested value**: Minium Y position of anything in the world that should be covered by the nav mesh.
+    pub world_bottom_bound: f32,
+
+    /// Maximum incline/slope traversable when navigating in radians.
+    pub max_traversable_slope_radians: f32,
+    /// Minimum open height for an area to be considered walkable in cell_height(s).
+    ///
+    /// **Suggested value**: The height of character * ``cell_height``, rounded up.
+    pub walkable_height: u16,
+    /// This will "pull-back" the nav-mesh from edges, meaning anywhere on the nav-mesh will be walkable for a character with a radius of ``walkable_radius * cell_width``.
+    ///
+    /// **Suggested value**: ``ceil(character_radius / cell_width)`` (2-3 if `cell_width` is 1/2 of ``character_radius``)  
+    pub walkable_radius: u16,
+    /// Maximum height difference that is still considered traversable in cell_height(s). This smooths out stair steps and small ledges.
+    pub step_height: u16,
+
+    /// Minimum size of a region in cells, anything smaller than this will be removed. This is used to filter out smaller disconnected island that may appear on surfaces like tables.
+    pub min_region_area: u32,
+    /// Maximum size of a region in cells we can merge other regions into.a
+    pub max_region_area_to_merge_into: u32,
+
+    /// Maximum length of an edge before it's split.
+    ///
+    /// **Suggested value**: Start high and reduce if there are issues.
+    pub max_edge_length: u16,
+    /// Maximum difference allowed for simplified contour generation on the XZ-plane in cell_width(s).
+    ///
+    /// **Suggested value range**: `[1.1, 1.5]`
+    pub max_contour_simplification_error: f32,
+
+    /// Max tiles to generate in parallel at once. A value of ``None`` will result in no limit.
+    ///
+    /// Adjust this to control memory & CPU usage. More tiles generating at once will have a higher memory footprint.
+    pub max_tile_generation_tasks: Option<NonZeroU16>,
+    /// Max tasks a single tile's rasterization is split into. A value of ``None`` will use up to one task per thread in the [AsyncComputeTaskPool].
+    ///
+    /// Only tiles with a lot of triangles, such as large terrain, are split up. Set to ``1`` to always rasterize on the tile's own task.
+    pub max_rasterization_tasks: Option<NonZeroU16>,
+
+    /// Keep each tile's eroded open heightfield in memory. Changes to [NavMeshObstacle]s & [NavMeshAreaVolume]s then only rerun region, contour & mesh generation instead of rebuilding the tile from its geometry.
+    ///
+    /// **Suggested value**: ``true`` if you use obstacles & can spare the memory. Each cached tile is roughly ``tile_width^2 * 50`` bytes.
+    pub cache_open_tiles: bool,
+
+    /// Generate [tiles::OffMeshLink]s for ledges agents can drop down from & gaps they can jump across. A value of ``None`` disables link generation.
+    pub jump_links: Option<JumpLinkSettings>,
+
+    /// Algorithm used to partition tiles into regions.
+    ///
+    /// **Suggested value**: [RegionPartitioning::Watershed], or [RegionPartitioning::Monotone] if tiles are rebuilt often.
+    pub partitioning: RegionPartitioning,
+
+    /// Filters applied to the voxelized tile. See [SpanFilters].
+    ///
+    /// **Suggested value**: [SpanFilters::ALL]
+    pub span_filters: SpanFilters,
+
+    /// Replace the area of each walkable cell with the median of it & its neighbours after erosion. Smooths out jagged borders between areas, leading to fewer sliver polygons. [NavMeshAreaVolume]s & obstacles are applied afterwards & aren't affected.
+    pub median_area_filter: bool,
+
+    /// Subdivisions used for curved colliders. Can be overridden per affector with [NavMeshTessellation].
+    ///
+    /// **Suggested value**: ``Tessellation::Fixed(5)`` for small shapes, [Tessellation::Adaptive] for worlds with both large & small curved shapes.
+    pub tessellation: Tessellation,
+
+    /// Only generate tiles around [NavMeshStreamingAnchor]s. A value of ``None`` generates every tile with affectors.
+    pub streaming: Option<StreamingSettings>,
+
+    /// Wait until a tile's affectors have been still for this long before rebuilding it. Avoids rebuilding a tile every frame while an affector is moving. A value of ``None`` rebuilds tiles as soon as possible.
+    pub affector_debounce: Option<Duration>,
+
+    /// Gather each tile's affectors, obstacles, area volumes & blockers sorted by their ``GlobalTransform`` & keep polygon links in a fixed order, so identical inputs always produce identical tiles regardless of generation order.
+    ///
+    /// **Note:** Entities sharing the exact same ``GlobalTransform`` are ordered by [Entity], which depends on spawn & despawn history. Spawn those in the same order between runs.
+    ///
+    /// **Suggested value**: ``true`` if polygon indices need to match between runs or machines, for example for replays or lockstep networking.
+    pub deterministic: bool,
+}
+impl NavMeshSettings {
+    /// Helper function for creating nav-mesh settings with reasonable defaults from the size of your navigation agent and bounds of your world.
+    ///
+    ///
+    #[inline]
+    pub fn from_agent_and_bounds(
+        agent_radius: f32,
+        agent_height: f32,
+        world_half_extents: f32,
+        world_bottom_bound: f32,
+    ) -> Self {
+        let cell_width = agent_radius / 2.0;
+        let cell_height = agent_radius / 4.0;
+
+        let walkable_height = (agent_height / cell_height) as u16;
+
+        Self {
+            cell_width,
+            cell_height,
+            tile_width: 120,
+            world_bounds: Rect::from_center_half_size(
+                Vec2::ZERO,
+                Vec2::splat(world_half_extents.abs()),
+            ),
+            world_bottom_bound,
+            max_traversable_slope_radians: 50.0_f32.to_radians(),
+            walkable_height,
+            walkable_radius: 2,
+            step_height: 3,
+            min_region_area: 100,
+            max_region_area_to_merge_into: 500,
+            max_edge_length: 80,
+            max_contour_simplification_error: 1.1,
+            max_tile_generation_tasks: NonZeroU16::new(8),
+            max_rasterization_tasks: None,
+            cache_open_tiles: false,
+            jump_links: None,
+            partitioning: RegionPartitioning::Watershed,
+            span_filters: SpanFilters::default(),
+            median_area_filter: false,
+            tessellation: Tessellation::default(),
+            streaming: None,
+            affector_debounce: None,
+            deterministic: false,
+        }
+    }
+    /// Setter for [`NavMeshSettings::walkable_radius`]
+    pub fn with_walkable_radius(mut self, walkable_radius: u16) -> Self {
+        self.walkable_radius = walkable_radius;
 
         self
     }
@@ -329,6 +1292,75 @@ impl NavMeshSettings {
 
         self
     }
+    /// Setter for [`NavMeshSettings::cache_open_tiles`]
+    pub fn with_open_tile_cache(mut self, cache_open_tiles: bool) -> Self {
+        self.cache_open_tiles = cache_open_tiles;
+
+        self
+    }
+    /// Setter for [`NavMeshSettings::jump_links`]
+    pub fn with_jump_links(mut self, jump_links: Option<JumpLinkSettings>) -> Self {
+        self.jump_links = jump_links;
+
+        self
+    }
+    /// Setter for [`NavMeshSettings::partitioning`]
+    pub fn with_partitioning(mut self, partitioning: RegionPartitioning) -> Self {
+        self.partitioning = partitioning;
+
+        self
+    }
+    /// Setter for [`NavMeshSettings::span_filters`]
+    pub fn with_span_filters(mut self, span_filters: SpanFilters) -> Self {
+        self.span_filters = span_filters;
+
+        self
+    }
+    /// Setter for [`NavMeshSettings::median_area_filter`]
+    pub fn with_median_area_filter(mut self, median_area_filter: bool) -> Self {
+        self.median_area_filter = median_area_filter;
+
+        self
+    }
+    /// Setter for [`NavMeshSettings::tessellation`]
+    pub fn with_tessellation(mut self, tessellation: Tessellation) -> Self {
+        self.tessellation = tessellation;
+
+        self
+    }
+    /// Setter for [`NavMeshSettings::streaming`]
+    pub fn with_streaming(mut self, streaming: Option<StreamingSettings>) -> Self {
+        self.streaming = streaming;
+
+        self
+    }
+    /// Setter for [`NavMeshSettings::affector_debounce`]
+    pub fn with_affector_debounce(mut self, affector_debounce: Option<Duration>) -> Self {
+        self.affector_debounce = affector_debounce;
+
+        self
+    }
+    /// Setter for [`NavMeshSettings::deterministic`]
+    pub fn with_deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+
+        self
+    }
+    /// Setter for [`NavMeshSettings::world_bounds`]
+    pub fn with_world_bounds(mut self, world_bounds: Rect) -> Self {
+        self.world_bounds = world_bounds;
+
+        self
+    }
+    /// Setter for [`NavMeshSettings::max_rasterization_tasks`]
+    pub fn with_max_rasterization_tasks(
+        mut self,
+        max_rasterization_tasks: Option<NonZeroU16>,
+    ) -> Self {
+        self.max_rasterization_tasks = max_rasterization_tasks;
+
+        self
+    }
     /// Setter for [`NavMeshSettings::step_height`]
     pub fn with_step_height(mut self, step_height: u16) -> Self {
         self.step_height = step_height;
@@ -373,17 +1405,50 @@ impl NavMeshSettings {
     }
 
     /// Returns the tile coordinate that contains the supplied ``world_position``.
+    ///
+    /// Positions outside [NavMeshSettings::world_bounds] are clamped to the closest tile within them, check [NavMeshSettings::is_within_world_bounds] first if that isn't wanted.
     #[inline]
     pub fn get_tile_containing_position(&self, world_position: Vec2) -> UVec2 {
-        let offset_world = world_position + self.world_half_extents;
+        let offset_world = world_position - self.world_bounds.min;
+
+        (offset_world / self.get_tile_size())
+            .as_uvec2()
+            .min(self.get_max_tile())
+    }
+
+    /// Returns the coordinate of the last tile within [NavMeshSettings::world_bounds].
+    #[inline]
+    pub fn get_max_tile(&self) -> UVec2 {
+        (self.world_bounds.size() / self.get_tile_size())
+            .ceil()
+            .as_uvec2()
+            .saturating_sub(UVec2::ONE)
+    }
+
+    /// Returns true if ``world_position`` is within [NavMeshSettings::world_bounds].
+    #[inline]
+    pub fn is_within_world_bounds(&self, world_position: Vec2) -> bool {
+        self.world_bounds.contains(world_position)
+    }
 
-        (offset_world / self.get_tile_size()).as_uvec2()
+    /// Returns the minimum & maximum tile overlapping the area from ``min`` to ``max`` or ``None`` if it's entirely outside [NavMeshSettings::world_bounds].
+    #[inline]
+    pub fn get_tiles_overlapping(&self, min: Vec2, max: Vec2) -> Option<(UVec2, UVec2)> {
+        let overlap = self.world_bounds.intersect(Rect::from_corners(min, max));
+        if overlap.min.cmpgt(overlap.max).any() {
+            return None;
+        }
+
+        Some((
+            self.get_tile_containing_position(overlap.min),
+            self.get_tile_containing_position(overlap.max),
+        ))
     }
 
     /// Returns the minimum bound of a tile on the XZ-plane.
     #[inline]
     pub fn get_tile_origin(&self, tile: UVec2) -> Vec2 {
-        tile.as_vec2() * self.get_tile_size() - self.world_half_extents
+        tile.as_vec2() * self.get_tile_size() + self.world_bounds.min
     }
 
     /// Returns the origin of a tile on the XZ-plane including the border area.
@@ -407,7 +1472,7 @@ impl NavMeshSettings {
     pub fn get_tile_bounds(&self, tile: UVec2) -> (Vec2, Vec2) {
         let tile_size = self.get_tile_size();
 
-        let min_bound = tile.as_vec2() * tile_size - self.world_half_extents;
+        let min_bound = tile.as_vec2() * tile_size + self.world_bounds.min;
         let max_bound = min_bound + tile_size;
 
         (min_bound, max_bound)
@@ -426,18 +1491,25 @@ impl NavMesh {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn update_navmesh_affectors_system<C: OxidizedCollider>(
     nav_mesh_settings: Res<NavMeshSettings>,
+    time: Res<Time>,
     mut tile_affectors: ResMut<TileAffectors>,
     mut affector_relations: ResMut<NavMeshAffectorRelations>,
     mut dirty_tiles: ResMut<DirtyTiles>,
+    mut debounced_tiles: ResMut<DebouncedTiles>,
+    mut static_layer_cache: ResMut<StaticLayerCache>,
+    mut out_of_world_bounds: EventWriter<OutOfWorldBounds>,
     mut query: Query<
-        (Entity, &C, &GlobalTransform),
+        (Entity, &C, &GlobalTransform, Has<NavMeshStaticAffector>),
         (
             Or<(
                 Changed<GlobalTransform>,
                 Changed<C>,
                 Changed<NavMeshAffector>,
+                Changed<NavMeshStaticAffector>,
+                Changed<NavMeshTessellation>,
             )>,
             With<NavMeshAffector>,
         ),
@@ -446,10 +1518,12 @@ fn update_navmesh_affectors_system<C: OxidizedCollider>(
     // Expand by 2 * walkable_radius to match with erode_walkable_area.
     let border_expansion =
         f32::from(nav_mesh_settings.walkable_radius * 2) * nav_mesh_settings.cell_width;
+    let debounce = nav_mesh_settings.affector_debounce.is_some();
+    let now = time.elapsed();
 
     query
         .iter_mut()
-        .for_each(|(e, collider, global_transform)| {
+        .for_each(|(e, collider, global_transform, is_static)| {
             let transform = global_transform.compute_transform();
             let iso = Isometry::new(
                 transform.translation.into(),
@@ -464,17 +1538,14 @@ fn update_navmesh_affectors_system<C: OxidizedCollider>(
                 ))
                 .transform_by(&iso);
 
-            let min_vec = Vec2::new(
-                aabb.mins.x - border_expansion,
-                aabb.mins.z - border_expansion,
+            let (min_tile, max_tile) = get_tile_range(
+                &nav_mesh_settings,
+                e,
+                Vec2::new(aabb.mins.x, aabb.mins.z),
+                Vec2::new(aabb.maxs.x, aabb.maxs.z),
+                border_expansion,
+                &mut out_of_world_bounds,
             );
-            let min_tile = nav_mesh_settings.get_tile_containing_position(min_vec);
-
-            let max_vec = Vec2::new(
-                aabb.maxs.x + border_expansion,
-                aabb.maxs.z + border_expansion,
-            );
-            let max_tile = nav_mesh_settings.get_tile_containing_position(max_vec);
 
             let relation = if let Some(relation) = affector_relations.0.get_mut(&e) {
                 // Remove from previous.
@@ -487,6 +1558,12 @@ fn update_navmesh_affectors_system<C: OxidizedCollider>(
                     if let Some(affectors) = tile_affectors.get_mut(old_tile) {
                         affectors.remove(&e);
                         dirty_tiles.0.insert(*old_tile);
+                        if debounce {
+                            debounced_tiles.0.insert(*old_tile, now);
+                        }
+                    }
+                    if is_static {
+                        static_layer_cache.invalidate(*old_tile);
                     }
                 }
                 relation.clear();
@@ -514,6 +1591,12 @@ fn update_navmesh_affectors_system<C: OxidizedCollider>(
 
                     relation.push(tile_coord);
                     dirty_tiles.0.insert(tile_coord);
+                    if debounce {
+                        debounced_tiles.0.insert(tile_coord, now);
+                    }
+                    if is_static {
+                        static_layer_cache.invalidate(tile_coord);
+                    }
                 }
             }
         });
@@ -523,6 +1606,7 @@ fn handle_removed_affectors_system(
     mut removed_affectors: RemovedComponents<NavMeshAffector>,
     mut affector_relations: ResMut<NavMeshAffectorRelations>,
     mut dirty_tiles: ResMut<DirtyTiles>,
+    mut static_layer_cache: ResMut<StaticLayerCache>,
 ) {
     for relations in removed_affectors
         .read()
@@ -530,10 +1614,100 @@ fn handle_removed_affectors_system(
     {
         for tile in relations {
             dirty_tiles.0.insert(tile);
+            // We can't tell if the removed affector was static anymore.
+            static_layer_cache.invalidate(tile);
         }
     }
 }
 
+fn handle_removed_static_affectors_system(
+    mut removed_static_affectors: RemovedComponents<NavMeshStaticAffector>,
+    affector_relations: Res<NavMeshAffectorRelations>,
+    mut dirty_tiles: ResMut<DirtyTiles>,
+    mut static_layer_cache: ResMut<StaticLayerCache>,
+) {
+    for relations in removed_static_affectors
+        .read()
+        .filter_map(|removed| affector_relations.0.get(&removed))
+    {
+        for tile in relations.iter() {
+            dirty_tiles.0.insert(*tile);
+            static_layer_cache.invalidate(*tile);
+        }
+    }
+}
+
+/// Finds the tiles in range of [NavMeshStreamingAnchor]s & removes loaded tiles that are out of range, marking them dirty so they're generated again once back in range.
+#[allow(clippy::too_many_arguments)]
+fn update_streamed_tiles_system(
+    nav_mesh_settings: Res<NavMeshSettings>,
+    mut streamed_tiles: ResMut<StreamedTiles>,
+    mut dirty_tiles: ResMut<DirtyTiles>,
+    mut active_generation_tasks: ResMut<ActiveGenerationTasks>,
+    mut generation_ticker: ResMut<GenerationTicker>,
+    mut tile_caches: TileCaches,
+    nav_mesh: Res<NavMesh>,
+    tile_affectors: Res<TileAffectors>,
+    anchor_query: Query<&GlobalTransform, With<NavMeshStreamingAnchor>>,
+) {
+    let Some(streaming) = nav_mesh_settings.streaming else {
+        return;
+    };
+
+    let anchors = anchor_query
+        .iter()
+        .map(|global_transform| global_transform.translation().xz())
+        .collect::<SmallVec<[Vec2; 4]>>();
+
+    let tile_size = nav_mesh_settings.get_tile_size();
+    let distance_to_tile = |anchor: Vec2, tile_coord: UVec2| {
+        let min = nav_mesh_settings.get_tile_origin(tile_coord);
+
+        anchor.distance(anchor.clamp(min, min + tile_size))
+    };
+
+    let StreamedTiles { in_range, loaded } = &mut *streamed_tiles;
+
+    in_range.clear();
+    for anchor in anchors.iter() {
+        let min_tile =
+            nav_mesh_settings.get_tile_containing_position(*anchor - streaming.load_radius);
+        let max_tile =
+            nav_mesh_settings.get_tile_containing_position(*anchor + streaming.load_radius);
+
+        for x in min_tile.x..=max_tile.x {
+            for y in min_tile.y..=max_tile.y {
+                let tile_coord = UVec2::new(x, y);
+                if distance_to_tile(*anchor, tile_coord) <= streaming.load_radius {
+                    in_range.insert(tile_coord);
+                }
+            }
+        }
+    }
+
+    loaded.retain(|tile_coord| {
+        let keep = anchors
+            .iter()
+            .any(|anchor| distance_to_tile(*anchor, *tile_coord) <= streaming.unload_radius);
+        if keep {
+            return true;
+        }
+
+        generation_ticker.0 += 1;
+        active_generation_tasks.supersede(*tile_coord);
+        tile_caches.remove_tile(generation_ticker.0, *tile_coord, &nav_mesh);
+
+        if tile_affectors
+            .get(tile_coord)
+            .is_some_and(|affectors| !affectors.is_empty())
+        {
+            dirty_tiles.0.insert(*tile_coord);
+        }
+
+        false
+    });
+}
+
 fn can_generate_new_tiles(
     active_generation_tasks: Res<ActiveGenerationTasks>,
     dirty_tiles: Res<DirtyTiles>,
@@ -550,63 +1724,80 @@ fn can_generate_new_tiles(
 fn send_tile_rebuild_tasks_system<C: OxidizedCollider>(
     mut active_generation_tasks: ResMut<ActiveGenerationTasks>,
     mut generation_ticker: ResMut<GenerationTicker>,
-    mut dirty_tiles: ResMut<DirtyTiles>,
-    mut tiles_to_generate: Local<Vec<UVec2>>,
-    mut heightfields: Local<EntityHashMap<Arc<HeightFieldCollection>>>,
     nav_mesh_settings: Res<NavMeshSettings>,
     nav_mesh: Res<NavMesh>,
-    tile_affectors: Res<TileAffectors>,
-    collider_query: Query<
-        (Entity, &C, &GlobalTransform, Option<&NavMeshAreaType>),
-        With<NavMeshAffector>,
-    >,
+    mut tile_queue: TileQueue,
+    mut tile_caches: TileCaches,
+    tile_inputs: TileInputs<C>,
 ) {
     let thread_pool = AsyncComputeTaskPool::get();
+    let TileInputs {
+        tile_affectors,
+        tile_modifiers,
+        collider_query,
+        mut heightfields,
+    } = tile_inputs;
 
     let max_task_count = (nav_mesh_settings
         .max_tile_generation_tasks
         .unwrap_or(NonZeroU16::MAX)
         .get() as usize)
         .saturating_sub(active_generation_tasks.0.len());
-    tiles_to_generate.extend(dirty_tiles.0.iter().take(max_task_count));
-
-    for tile_coord in tiles_to_generate.drain(..) {
-        dirty_tiles.0.remove(&tile_coord);
 
+    for tile_coord in tile_queue.take_ready(max_task_count, &nav_mesh_settings) {
         generation_ticker.0 += 1;
 
-        let Some(affectors) = tile_affectors.get(&tile_coord) else {
+        let Some(affectors) = tile_affectors
+            .get(&tile_coord)
+            .filter(|affectors| !affectors.is_empty())
+        else {
             // Spawn task to remove tile.
-            thread_pool
-                .spawn(remove_tile(
-                    generation_ticker.0,
-                    tile_coord,
-                    nav_mesh.0.clone(),
-                ))
-                .detach();
+            active_generation_tasks.supersede(tile_coord);
+            tile_caches.remove_tile(generation_ticker.0, tile_coord, &nav_mesh);
             continue;
         };
-        if affectors.is_empty() {
-            // Spawn task to remove tile.
-            thread_pool
-                .spawn(remove_tile(
-                    generation_ticker.0,
-                    tile_coord,
-                    nav_mesh.0.clone(),
-                ))
-                .detach();
-            continue;
-        }
 
         // Step 1: Gather data.
         let mut geometry_collections = Vec::with_capacity(affectors.len());
         // Storing heightfields separately because they are massive.
         let mut heightfield_collections = Vec::new();
 
-        let mut collider_iter = collider_query.iter_many(affectors.iter());
-        while let Some((entity, collider, global_transform, nav_mesh_affector)) =
-            collider_iter.fetch_next()
+        let cached_static_layer = tile_caches.static_layer_cache.get_valid_layer(tile_coord);
+        let mut static_geometry_collections = Vec::new();
+        let mut static_heightfield_collections = Vec::new();
+
+        let mut collider_iter = collider_query.iter_many(ordered_entities(
+            affectors,
+            nav_mesh_settings.deterministic,
+            |entity| {
+                collider_query
+                    .get(entity)
+                    .ok()
+                    .map(|(_, _, global_transform, ..)| *global_transform)
+            },
+        ));
+        while let Some((
+            entity,
+            collider,
+            global_transform,
+            nav_mesh_affector,
+            tessellation,
+            is_static,
+        )) = collider_iter.fetch_next()
         {
+            if is_static && cached_static_layer.is_some() {
+                // Already part of the cached layer.
+                continue;
+            }
+            let (geometry_collections, heightfield_collections) = if is_static {
+                (
+                    &mut static_geometry_collections,
+                    &mut static_heightfield_collections,
+                )
+            } else {
+                (&mut geometry_collections, &mut heightfield_collections)
+            };
+
             let area = nav_mesh_affector.map_or(Some(Area(0)), |area_type| area_type.0);
 
             let type_to_convert = match collider.oxidized_into_typed_shape() {
@@ -689,23 +1880,72 @@ fn send_tile_rebuild_tasks_system<C: OxidizedCollider>(
                 transform: global_transform.compute_transform(),
                 geometry_to_convert: type_to_convert,
                 area,
+                tessellation: tessellation.map(|tessellation| tessellation.0),
             });
         }
 
+        let static_layer = if let Some(static_layer) = cached_static_layer {
+            StaticLayer::Cached(static_layer)
+        } else if static_geometry_collections.is_empty()
+            && static_heightfield_collections.is_empty()
+        {
+            StaticLayer::None
+        } else {
+            StaticLayer::Build {
+                version: tile_caches.static_layer_cache.version(tile_coord),
+                geometry_collections: static_geometry_collections,
+                heightfields: static_heightfield_collections.into_boxed_slice(),
+                cache: tile_caches.static_layer_cache.layers.clone(),
+            }
+        };
+
+        let blockers = tile_modifiers.gather_blockers(tile_coord, nav_mesh_settings.deterministic);
+        let modifiers = tile_modifiers.gather(tile_coord, nav_mesh_settings.deterministic);
+
         // Step 2: Acquire nav_mesh lock
         let nav_mesh = nav_mesh.0.clone();
 
+        let open_tile_cache = if nav_mesh_settings.cache_open_tiles {
+            tile_caches
+                .open_tile_cache
+                .requested_generations
+                .insert(tile_coord, generation_ticker.0);
+
+            Some(tile_caches.open_tile_cache.tiles.clone())
+        } else {
+            None
+        };
+
+        // Only baked tiles waiting to be matched need their geometry hashed.
+        let baked_tiles = tile_caches
+            .baked_tile_cache
+            .tiles
+            .read()
+            .is_ok_and(|baked_tiles| baked_tiles.contains_key(&tile_coord))
+            .then(|| tile_caches.baked_tile_cache.tiles.clone());
+
         // Step 3: Make it a task.
+        let cancelled = active_generation_tasks.supersede(tile_coord);
         let task = thread_pool.spawn(build_tile(
             generation_ticker.0,
             tile_coord,
             nav_mesh_settings.clone(),
             geometry_collections,
             heightfield_collections.into_boxed_slice(),
+            static_layer,
+            blockers,
+            modifiers,
+            open_tile_cache,
+            baked_tiles,
             nav_mesh,
+            cancelled.clone(),
         ));
 
-        active_generation_tasks.0.push(task);
+        active_generation_tasks.0.push(GenerationTask {
+            tile_coord,
+            cancelled,
+            task,
+        });
     }
     heightfields.clear();
 }
@@ -716,12 +1956,22 @@ fn send_tile_rebuild_tasks_system<C: OxidizedCollider>(
 #[derive(Event)]
 pub struct TileGenerated(pub UVec2);
 
+/// Event sent when an entity is outside of [NavMeshSettings::world_bounds].
+///
+/// Sent for affectors, obstacles, area volumes & blockers that are partially or entirely outside, only the part within the bounds is added to the nav-mesh. Also sent for [agent::NavAgent]s & [crowd::CrowdAgent]s whose position or destination is outside, their paths will fail.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct OutOfWorldBounds {
+    pub entity: Entity,
+    /// The area on the XZ-plane that was checked against the world bounds.
+    pub bounds: Rect,
+}
+
 fn remove_finished_tasks(
     mut active_generation_tasks: ResMut<ActiveGenerationTasks>,
     mut event: EventWriter<TileGenerated>
 ) {
     active_generation_tasks.0.retain_mut(|task| {
-        if let Some(tile) = future::block_on(future::poll_once(task)) {
+        if let Some(tile) = future::block_on(future::poll_once(&mut task.task)) {
             if let Some(tile) = tile {
                 event.send(TileGenerated(tile));
             }
@@ -733,10 +1983,431 @@ fn remove_finished_tasks(
     });
 }
 
+fn update_nav_mesh_doors_system(
+    nav_mesh_settings: Res<NavMeshSettings>,
+    nav_mesh: Res<NavMesh>,
+    mut tile_generated: EventReader<TileGenerated>,
+    mut removed_doors: RemovedComponents<NavMeshDoor>,
+    mut applied_doors: Local<EntityHashMap<(Vec3, Vec3, u16)>>,
+    door_query: Query<(Entity, Ref<NavMeshDoor>, Ref<GlobalTransform>)>,
+) {
+    let generated_tiles: SmallVec<[(Vec2, Vec2); 4]> = tile_generated
+        .read()
+        .map(|event| nav_mesh_settings.get_tile_bounds(event.0))
+        .collect();
+
+    // Boxes & flags that need to be cleared. Any closed door overlapping one of these is reapplied afterwards.
+    let mut boxes_to_clear: SmallVec<[(Vec3, Vec3, u16); 4]> = removed_doors
+        .read()
+        .filter_map(|entity| applied_doors.remove(&entity))
+        .collect();
+
+    for (entity, door, global_transform) in door_query.iter() {
+        let (min, max) = door.get_bounds(&global_transform);
+
+        let tile_regenerated = generated_tiles.iter().any(|(tile_min, tile_max)| {
+            min.x <= tile_max.x && max.x >= tile_min.x && min.z <= tile_max.y && max.z >= tile_min.y
+        });
+        if !(door.is_changed() || global_transform.is_changed() || tile_regenerated) {
+            continue;
+        }
+
+        let applied = (min, max, door.closed_flags);
+        if let Some(previous) = applied_doors.insert(entity, applied) {
+            if previous != applied {
+                boxes_to_clear.push(previous);
+            }
+        }
+        boxes_to_clear.push(applied);
+    }
+
+    if boxes_to_clear.is_empty() {
+        return;
+    }
+
+    let Ok(mut nav_mesh) = nav_mesh.0.write() else {
+        error!("Nav-Mesh lock has been poisoned. Doors can no longer be updated.");
+        return;
+    };
+
+    for (min, max, flags) in boxes_to_clear.iter() {
+        nav_mesh.set_polygon_flags_in_box(&nav_mesh_settings, *min, *max, 0, *flags);
+    }
+
+    for (entity, _, _) in door_query.iter().filter(|(_, door, _)| !door.open) {
+        let Some((min, max, flags)) = applied_doors.get(&entity) else {
+            continue;
+        };
+
+        let overlaps_cleared = boxes_to_clear.iter().any(|(other_min, other_max, _)| {
+            min.cmple(*other_max).all() && max.cmpge(*other_min).all()
+        });
+        if overlaps_cleared {
+            nav_mesh.set_polygon_flags_in_box(&nav_mesh_settings, *min, *max, *flags, 0);
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn update_nav_mesh_obstacles_system(
+    nav_mesh_settings: Res<NavMeshSettings>,
+    mut tile_obstacles: ResMut<TileObstacles>,
+    mut obstacle_relations: ResMut<NavMeshObstacleRelations>,
+    mut dirty_obstacle_tiles: ResMut<DirtyObstacleTiles>,
+    mut out_of_world_bounds: EventWriter<OutOfWorldBounds>,
+    query: Query<
+        (Entity, &NavMeshObstacle, &GlobalTransform),
+        Or<(Changed<GlobalTransform>, Changed<NavMeshObstacle>)>,
+    >,
+) {
+    // Expand by 2 * walkable_radius, once for the expansion in carve_obstacles & once for the tile border.
+    let border_expansion =
+        f32::from(nav_mesh_settings.walkable_radius * 2) * nav_mesh_settings.cell_width;
+
+    for (e, obstacle, global_transform) in query.iter() {
+        let (min, max) = obstacle.get_bounds(&global_transform.compute_transform());
+
+        let (min_tile, max_tile) = get_tile_range(
+            &nav_mesh_settings,
+            e,
+            min.xz(),
+            max.xz(),
+            border_expansion,
+            &mut out_of_world_bounds,
+        );
+
+        relate_to_tiles(
+            e,
+            min_tile,
+            max_tile,
+            &mut tile_obstacles,
+            &mut obstacle_relations.0,
+            &mut dirty_obstacle_tiles.0,
+        );
+    }
+}
+
+fn handle_removed_obstacles_system(
+    mut removed_obstacles: RemovedComponents<NavMeshObstacle>,
+    mut obstacle_relations: ResMut<NavMeshObstacleRelations>,
+    mut tile_obstacles: ResMut<TileObstacles>,
+    mut dirty_obstacle_tiles: ResMut<DirtyObstacleTiles>,
+) {
+    for removed in removed_obstacles.read() {
+        unrelate_from_tiles(
+            removed,
+            &mut tile_obstacles,
+            &mut obstacle_relations.0,
+            &mut dirty_obstacle_tiles.0,
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn update_nav_mesh_area_volumes_system(
+    nav_mesh_settings: Res<NavMeshSettings>,
+    mut tile_area_volumes: ResMut<TileAreaVolumes>,
+    mut area_volume_relations: ResMut<NavMeshAreaVolumeRelations>,
+    mut dirty_obstacle_tiles: ResMut<DirtyObstacleTiles>,
+    mut out_of_world_bounds: EventWriter<OutOfWorldBounds>,
+    query: Query<
+        (Entity, &NavMeshAreaVolume, &GlobalTransform),
+        Or<(Changed<GlobalTransform>, Changed<NavMeshAreaVolume>)>,
+    >,
+) {
+    let border_expansion = nav_mesh_settings.get_border_size();
+
+    for (e, area_volume, global_transform) in query.iter() {
+        let (min, max) = area_volume
+            .shape
+            .get_bounds(&global_transform.compute_transform());
+
+        let (min_tile, max_tile) = get_tile_range(
+            &nav_mesh_settings,
+            e,
+            min.xz(),
+            max.xz(),
+            border_expansion,
+            &mut out_of_world_bounds,
+        );
+
+        relate_to_tiles(
+            e,
+            min_tile,
+            max_tile,
+            &mut tile_area_volumes,
+            &mut area_volume_relations.0,
+            &mut dirty_obstacle_tiles.0,
+        );
+    }
+}
+
+fn handle_removed_area_volumes_system(
+    mut removed_area_volumes: RemovedComponents<NavMeshAreaVolume>,
+    mut area_volume_relations: ResMut<NavMeshAreaVolumeRelations>,
+    mut tile_area_volumes: ResMut<TileAreaVolumes>,
+    mut dirty_obstacle_tiles: ResMut<DirtyObstacleTiles>,
+) {
+    for removed in removed_area_volumes.read() {
+        unrelate_from_tiles(
+            removed,
+            &mut tile_area_volumes,
+            &mut area_volume_relations.0,
+            &mut dirty_obstacle_tiles.0,
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn update_nav_mesh_blockers_system(
+    nav_mesh_settings: Res<NavMeshSettings>,
+    mut tile_blockers: ResMut<TileBlockers>,
+    mut blocker_relations: ResMut<NavMeshBlockerRelations>,
+    mut dirty_tiles: ResMut<DirtyTiles>,
+    mut out_of_world_bounds: EventWriter<OutOfWorldBounds>,
+    query: Query<
+        (Entity, &NavMeshBlocker, &GlobalTransform),
+        Or<(Changed<GlobalTransform>, Changed<NavMeshBlocker>)>,
+    >,
+) {
+    let border_expansion = nav_mesh_settings.get_border_size();
+
+    for (e, blocker, global_transform) in query.iter() {
+        let (min, max) = blocker
+            .shape
+            .get_bounds(&global_transform.compute_transform());
+
+        let (min_tile, max_tile) = get_tile_range(
+            &nav_mesh_settings,
+            e,
+            min.xz(),
+            max.xz(),
+            border_expansion,
+            &mut out_of_world_bounds,
+        );
+
+        relate_to_tiles(
+            e,
+            min_tile,
+            max_tile,
+            &mut tile_blockers,
+            &mut blocker_relations.0,
+            &mut dirty_tiles.0,
+        );
+    }
+}
+
+fn handle_removed_blockers_system(
+    mut removed_blockers: RemovedComponents<NavMeshBlocker>,
+    mut blocker_relations: ResMut<NavMeshBlockerRelations>,
+    mut tile_blockers: ResMut<TileBlockers>,
+    mut dirty_tiles: ResMut<DirtyTiles>,
+) {
+    for removed in removed_blockers.read() {
+        unrelate_from_tiles(
+            removed,
+            &mut tile_blockers,
+            &mut blocker_relations.0,
+            &mut dirty_tiles.0,
+        );
+    }
+}
+
+/// Returns the tiles from ``min`` to ``max`` expanded by ``border_expansion``. The range is empty if it's entirely outside [NavMeshSettings::world_bounds].
+///
+/// Sends [OutOfWorldBounds] if ``entity`` isn't entirely within the world bounds.
+fn get_tile_range(
+    nav_mesh_settings: &NavMeshSettings,
+    entity: Entity,
+    min: Vec2,
+    max: Vec2,
+    border_expansion: f32,
+    out_of_world_bounds: &mut EventWriter<OutOfWorldBounds>,
+) -> (UVec2, UVec2) {
+    if !nav_mesh_settings.is_within_world_bounds(min)
+        || !nav_mesh_settings.is_within_world_bounds(max)
+    {
+        out_of_world_bounds.send(OutOfWorldBounds {
+            entity,
+            bounds: Rect::from_corners(min, max),
+        });
+    }
+
+    nav_mesh_settings
+        .get_tiles_overlapping(min - border_expansion, max + border_expansion)
+        .unwrap_or((UVec2::ONE, UVec2::ZERO))
+}
+
+/// Moves ``entity`` to the tiles from ``min_tile`` to ``max_tile``. Unlike affectors the tiles are always dirtied, both the ones it left & the ones it's now in.
+fn relate_to_tiles(
+    entity: Entity,
+    min_tile: UVec2,
+    max_tile: UVec2,
+    tile_entities: &mut HashMap<UVec2, HashSet<Entity>>,
+    relations: &mut EntityHashMap<SmallVec<[UVec2; 4]>>,
+    dirty_tiles: &mut HashSet<UVec2>,
+) {
+    let relation = if let Some(relation) = relations.get_mut(&entity) {
+        // Remove from previous.
+        for old_tile in relation.iter() {
+            if let Some(entities) = tile_entities.get_mut(old_tile) {
+                entities.remove(&entity);
+            }
+            dirty_tiles.insert(*old_tile);
+        }
+        relation.clear();
+
+        relation
+    } else {
+        relations
+            .insert_unique_unchecked(entity, SmallVec::default())
+            .1
+    };
+
+    for x in min_tile.x..=max_tile.x {
+        for y in min_tile.y..=max_tile.y {
+            let tile_coord = UVec2::new(x, y);
+
+            tile_entities.entry(tile_coord).or_default().insert(entity);
+
+            relation.push(tile_coord);
+            dirty_tiles.insert(tile_coord);
+        }
+    }
+}
+
+fn unrelate_from_tiles(
+    entity: Entity,
+    tile_entities: &mut HashMap<UVec2, HashSet<Entity>>,
+    relations: &mut EntityHashMap<SmallVec<[UVec2; 4]>>,
+    dirty_tiles: &mut HashSet<UVec2>,
+) {
+    let Some(relation) = relations.remove(&entity) else {
+        return;
+    };
+
+    for tile in relation {
+        if let Some(entities) = tile_entities.get_mut(&tile) {
+            entities.remove(&entity);
+        }
+        dirty_tiles.insert(tile);
+    }
+}
+
+fn can_rebuild_obstacle_tiles(
+    active_generation_tasks: Res<ActiveGenerationTasks>,
+    dirty_obstacle_tiles: Res<DirtyObstacleTiles>,
+    nav_mesh_settings: Res<NavMeshSettings>,
+) -> bool {
+    let max_task_count = nav_mesh_settings
+        .max_tile_generation_tasks
+        .unwrap_or(NonZeroU16::MAX)
+        .get() as usize;
+
+    active_generation_tasks.0.len() < max_task_count && !dirty_obstacle_tiles.0.is_empty()
+}
+
+/// Rebuilds tiles whose obstacles or area volumes have changed from [OpenTileCache] when possible, otherwise marks them for a full rebuild.
+#[allow(clippy::too_many_arguments)]
+fn send_obstacle_rebuild_tasks_system(
+    mut active_generation_tasks: ResMut<ActiveGenerationTasks>,
+    mut generation_ticker: ResMut<GenerationTicker>,
+    mut dirty_tiles: ResMut<DirtyTiles>,
+    mut dirty_obstacle_tiles: ResMut<DirtyObstacleTiles>,
+    mut tiles_to_generate: Local<Vec<UVec2>>,
+    nav_mesh_settings: Res<NavMeshSettings>,
+    nav_mesh: Res<NavMesh>,
+    tile_modifiers: TileModifiers,
+    tile_prioritizer: TilePrioritizer,
+    open_tile_cache: Res<OpenTileCache>,
+) {
+    if !nav_mesh_settings.cache_open_tiles {
+        // Without the cache every obstacle change requires a full rebuild.
+        dirty_tiles.0.extend(dirty_obstacle_tiles.0.drain());
+        return;
+    }
+
+    let thread_pool = AsyncComputeTaskPool::get();
+
+    let mut max_task_count = (nav_mesh_settings
+        .max_tile_generation_tasks
+        .unwrap_or(NonZeroU16::MAX)
+        .get() as usize)
+        .saturating_sub(active_generation_tasks.0.len());
+
+    let Ok(cached_tiles) = open_tile_cache.tiles.read() else {
+        error!("Open tile cache lock has been poisoned. Falling back to full tile rebuilds.");
+        dirty_tiles.0.extend(dirty_obstacle_tiles.0.drain());
+        return;
+    };
+
+    tiles_to_generate.extend(dirty_obstacle_tiles.0.iter());
+    let tile_count = tiles_to_generate.len();
+    tile_prioritizer.prioritize(&mut tiles_to_generate, tile_count, &nav_mesh_settings);
+    for tile_coord in tiles_to_generate.drain(..) {
+        if dirty_tiles.0.contains(&tile_coord) {
+            // The full rebuild will pick up the obstacles & area volumes.
+            dirty_obstacle_tiles.0.remove(&tile_coord);
+            continue;
+        }
+
+        let Some(requested_generation) = open_tile_cache.requested_generations.get(&tile_coord)
+        else {
+            // No geometry in this tile, nothing to modify.
+            dirty_obstacle_tiles.0.remove(&tile_coord);
+            continue;
+        };
+
+        let Some((_, open_tile)) = cached_tiles
+            .get(&tile_coord)
+            .filter(|(cached_generation, _)| cached_generation >= requested_generation)
+        else {
+            if !active_generation_tasks.contains(tile_coord) {
+                // The rebuild finished without filling the cache, for example because a baked tile was used instead.
+                dirty_obstacle_tiles.0.remove(&tile_coord);
+                dirty_tiles.0.insert(tile_coord);
+            }
+            // Otherwise a full rebuild is still in progress, wait for it to fill the cache.
+            continue;
+        };
+
+        if max_task_count == 0 {
+            break;
+        }
+        max_task_count -= 1;
+
+        dirty_obstacle_tiles.0.remove(&tile_coord);
+
+        generation_ticker.0 += 1;
+
+        let modifiers = tile_modifiers.gather(tile_coord, nav_mesh_settings.deterministic);
+
+        let cancelled = active_generation_tasks.supersede(tile_coord);
+        let task = thread_pool.spawn(rebuild_tile_from_cache(
+            generation_ticker.0,
+            tile_coord,
+            nav_mesh_settings.clone(),
+            open_tile.clone(),
+            modifiers,
+            nav_mesh.0.clone(),
+            cancelled.clone(),
+        ));
+
+        active_generation_tasks.0.push(GenerationTask {
+            tile_coord,
+            cancelled,
+            task,
+        });
+    }
+}
+
 async fn remove_tile(
     generation: u64, // This is the max generation we remove. Should we somehow strangely be executing this after a new tile has arrived we won't remove it.
     tile_coord: UVec2,
     nav_mesh: Arc<RwLock<NavMeshTiles>>,
+    open_tile_cache: CachedOpenTiles,
+    static_layer_cache: CachedStaticLayers,
+    baked_tiles: CachedBakedTiles,
 ) {
     let Ok(mut nav_mesh) = nav_mesh.write() else {
         error!("Nav-Mesh lock has been poisoned. Generation can no longer be continued.");
@@ -746,23 +2417,253 @@ async fn remove_tile(
     if nav_mesh.tile_generations.get(&tile_coord).unwrap_or(&0) < &generation {
         nav_mesh.tile_generations.insert(tile_coord, generation);
         nav_mesh.remove_tile(tile_coord);
+
+        if let Ok(mut open_tile_cache) = open_tile_cache.write() {
+            open_tile_cache.remove(&tile_coord);
+        }
+        if let Ok(mut static_layer_cache) = static_layer_cache.write() {
+            static_layer_cache.remove(&tile_coord);
+        }
+        if let Ok(mut baked_tiles) = baked_tiles.write() {
+            baked_tiles.remove(&tile_coord);
+        }
     }
 }
+#[allow(clippy::too_many_arguments)]
 async fn build_tile(
     generation: u64,
     tile_coord: UVec2,
     nav_mesh_settings: NavMeshSettings,
     geometry_collections: Vec<GeometryCollection>,
     heightfields: Box<[Arc<HeightFieldCollection>]>,
+    static_layer: StaticLayer,
+    blockers: Vec<BlockerCollection>,
+    modifiers: OpenTileModifiers,
+    open_tile_cache: Option<CachedOpenTiles>,
+    baked_tiles: Option<CachedBakedTiles>,
     nav_mesh: Arc<RwLock<NavMeshTiles>>,
+    cancelled: Arc<AtomicBool>,
 ) -> Option<UVec2> {
     #[cfg(feature = "trace")]
     let _span = info_span!("Async build Tile").entered();
 
-    let nav_mesh_tile = build_tile_sync(
-        geometry_collections,
+    let is_cancelled = || cancelled.load(atomic::Ordering::Relaxed);
+    if is_cancelled() {
+        return None;
+    }
+
+    let triangle_collection = {
+        #[cfg(feature = "trace")]
+        let _span = info_span!("Convert Geometry Collections").entered();
+        convert_geometry_collections(geometry_collections, &nav_mesh_settings)
+    };
+
+    let (static_layer, static_layer_to_build) = match static_layer {
+        StaticLayer::None => (None, None),
+        StaticLayer::Cached(static_layer) => (Some(static_layer), None),
+        StaticLayer::Build {
+            version,
+            geometry_collections,
+            heightfields,
+            cache,
+        } => {
+            let triangle_collection =
+                convert_geometry_collections(geometry_collections, &nav_mesh_settings);
+
+            (
+                None,
+                Some((version, triangle_collection, heightfields, cache)),
+            )
+        }
+    };
+
+    // Tiles affected by modifiers are never baked with a hash.
+    if let Some(baked_tiles) = baked_tiles.filter(|_| blockers.is_empty() && modifiers.is_empty()) {
+        #[cfg(feature = "trace")]
+        let _span = info_span!("Hash tile geometry").entered();
+
+        let static_hash = match (&static_layer, &static_layer_to_build) {
+            (Some(static_layer), _) => static_layer.input_hash,
+            (None, Some((_, triangle_collection, heightfields, _))) => hash_tile_geometry(
+                tile_coord,
+                triangle_collection,
+                heightfields,
+                &nav_mesh_settings,
+            ),
+            (None, None) => 0,
+        };
+        let input_hash = static_hash.wrapping_add(hash_tile_geometry(
+            tile_coord,
+            &triangle_collection,
+            &heightfields,
+            &nav_mesh_settings,
+        ));
+
+        if let Some(generated) = use_baked_tile(
+            generation,
+            tile_coord,
+            input_hash,
+            &baked_tiles,
+            &nav_mesh,
+            &nav_mesh_settings,
+        ) {
+            return generated;
+        }
+    }
+    if is_cancelled() {
+        return None;
+    }
+
+    let static_layer = match static_layer_to_build {
+        None => static_layer,
+        Some((version, triangle_collection, heightfields, cache)) => {
+            #[cfg(feature = "trace")]
+            let _span = info_span!("Build static layer").entered();
+
+            let static_layer = Arc::new(build_heightfield_tile(
+                tile_coord,
+                &triangle_collection,
+                &heightfields,
+                &nav_mesh_settings,
+            ));
+
+            if let Ok(mut cache) = cache.write() {
+                // Don't overwrite a layer built for a newer version.
+                let has_newer = cache
+                    .get(&tile_coord)
+                    .is_some_and(|(cached_version, _)| *cached_version > version);
+                if !has_newer {
+                    cache.insert(tile_coord, (version, static_layer.clone()));
+                }
+            }
+
+            Some(static_layer)
+        }
+    };
+    if is_cancelled() {
+        return None;
+    }
+
+    let open_tile = build_eroded_open_tile(
+        &triangle_collection,
         tile_coord,
         heightfields,
+        static_layer.as_deref(),
+        &blockers,
+        &nav_mesh_settings,
+    );
+    if is_cancelled() {
+        return None;
+    }
+
+    // Only clone the open tile when there is something to cache.
+    let (nav_mesh_tile, open_tile) = if open_tile_cache.is_some() {
+        let open_tile = Arc::new(open_tile);
+        let nav_mesh_tile = build_tile_from_open_tile(
+            (*open_tile).clone(),
+            tile_coord,
+            &modifiers,
+            &nav_mesh_settings,
+        );
+
+        (nav_mesh_tile, Some(open_tile))
+    } else {
+        let nav_mesh_tile =
+            build_tile_from_open_tile(open_tile, tile_coord, &modifiers, &nav_mesh_settings);
+
+        (nav_mesh_tile, None)
+    };
+
+    let Ok(mut nav_mesh) = nav_mesh.write() else {
+        error!("Nav-Mesh lock has been poisoned. Generation can no longer be continued.");
+        return None;
+    };
+
+    if nav_mesh.tile_generations.get(&tile_coord).unwrap_or(&0) < &generation {
+        nav_mesh.tile_generations.insert(tile_coord, generation);
+
+        nav_mesh.add_tile(tile_coord, nav_mesh_tile, &nav_mesh_settings);
+
+        if let Some((open_tile_cache, open_tile)) = open_tile_cache.zip(open_tile) {
+            if let Ok(mut open_tile_cache) = open_tile_cache.write() {
+                open_tile_cache.insert(tile_coord, (generation, open_tile));
+            }
+        }
+
+        Some(tile_coord)
+    } else {
+        None
+    }
+}
+
+/// Checks ``input_hash`` against the baked tile loaded for ``tile_coord``. Returns ``None`` if they don't match & the tile needs to be generated, otherwise what the generation task should return.
+///
+/// If the tile has been regenerated since it was loaded the baked tile is put back. The baked tile is discarded once it hasn't matched more than [MAX_BAKED_TILE_MISMATCHES] times, so tiles that keep changing aren't hashed on every rebuild.
+fn use_baked_tile(
+    generation: u64,
+    tile_coord: UVec2,
+    input_hash: u64,
+    baked_tiles: &CachedBakedTiles,
+    nav_mesh: &Arc<RwLock<NavMeshTiles>>,
+    nav_mesh_settings: &NavMeshSettings,
+) -> Option<Option<UVec2>> {
+    let baked_tile = {
+        let mut baked_tiles = baked_tiles.write().ok()?;
+        let baked_tile = baked_tiles.get_mut(&tile_coord)?;
+        if baked_tile.input_hash != input_hash {
+            if baked_tile.mismatches_left == 0 {
+                baked_tiles.remove(&tile_coord);
+            } else {
+                baked_tile.mismatches_left -= 1;
+            }
+
+            return None;
+        }
+
+        baked_tiles.remove(&tile_coord)?
+    };
+
+    let Ok(mut nav_mesh) = nav_mesh.write() else {
+        error!("Nav-Mesh lock has been poisoned. Generation can no longer be continued.");
+        return Some(None);
+    };
+
+    let current_generation = nav_mesh
+        .tile_generations
+        .get(&tile_coord)
+        .copied()
+        .unwrap_or(0);
+    if current_generation == baked_tile.generation || current_generation >= generation {
+        // Either the baked tile is still in the nav-mesh or a newer tile has replaced it.
+        return Some(None);
+    }
+
+    nav_mesh.tile_generations.insert(tile_coord, generation);
+    nav_mesh.add_tile(tile_coord, baked_tile.tile, nav_mesh_settings);
+
+    Some(Some(tile_coord))
+}
+
+async fn rebuild_tile_from_cache(
+    generation: u64,
+    tile_coord: UVec2,
+    nav_mesh_settings: NavMeshSettings,
+    open_tile: Arc<OpenTile>,
+    modifiers: OpenTileModifiers,
+    nav_mesh: Arc<RwLock<NavMeshTiles>>,
+    cancelled: Arc<AtomicBool>,
+) -> Option<UVec2> {
+    #[cfg(feature = "trace")]
+    let _span = info_span!("Async rebuild Tile from cache").entered();
+
+    if cancelled.load(atomic::Ordering::Relaxed) {
+        return None;
+    }
+
+    let nav_mesh_tile = build_tile_from_open_tile(
+        (*open_tile).clone(),
+        tile_coord,
+        &modifiers,
         &nav_mesh_settings,
     );
 
@@ -775,7 +2676,7 @@ async fn build_tile(
         nav_mesh.tile_generations.insert(tile_coord, generation);
 
         nav_mesh.add_tile(tile_coord, nav_mesh_tile, &nav_mesh_settings);
-        
+
         Some(tile_coord)
     } else {
         None
@@ -788,21 +2689,55 @@ pub fn build_tile_sync(
     heightfields: Box<[Arc<HeightFieldCollection>]>,
     nav_mesh_settings: &NavMeshSettings,
 ) -> NavMeshTile {
-    let triangle_collection = {
-        #[cfg(feature = "trace")]
-        let _span = info_span!("Convert Geometry Collections").entered();
-        convert_geometry_collections(geometry_collections)
-    };
+    let triangle_collection = convert_geometry_collections(geometry_collections, nav_mesh_settings);
+    let open_tile = build_eroded_open_tile(
+        &triangle_collection,
+        tile_coord,
+        heightfields,
+        None,
+        &[],
+        nav_mesh_settings,
+    );
 
+    build_tile_from_open_tile(
+        open_tile,
+        tile_coord,
+        &OpenTileModifiers::default(),
+        nav_mesh_settings,
+    )
+}
+
+/// Voxelizes the geometry on top of the optional static layer, applies the blockers & erodes the walkable area. The result doesn't depend on any obstacles or area volumes & is what [OpenTileCache] stores.
+fn build_eroded_open_tile(
+    triangle_collection: &[TriangleCollection],
+    tile_coord: UVec2,
+    heightfields: Box<[Arc<HeightFieldCollection>]>,
+    static_layer: Option<&VoxelizedTile>,
+    blockers: &[BlockerCollection],
+    nav_mesh_settings: &NavMeshSettings,
+) -> OpenTile {
     let voxelized_tile = {
         #[cfg(feature = "trace")]
         let _span = info_span!("Build Heightfield Tile").entered();
-        build_heightfield_tile(
-            tile_coord,
-            &triangle_collection,
-            &heightfields,
-            nav_mesh_settings,
-        )
+        if let Some(static_layer) = static_layer {
+            let mut voxelized_tile = static_layer.clone();
+            rasterize_into_heightfield_tile(
+                &mut voxelized_tile,
+                tile_coord,
+                triangle_collection,
+                &heightfields,
+                nav_mesh_settings,
+            );
+
+            voxelized_tile
+        } else {
+            build_heightfield_tile(
+                tile_coord,
+                triangle_collection,
+                &heightfields,
+                nav_mesh_settings,
+            )
+        }
     };
 
     let mut open_tile = {
@@ -811,6 +2746,14 @@ pub fn build_tile_sync(
         build_open_heightfield_tile(voxelized_tile, nav_mesh_settings)
     };
 
+    // Blockers are applied before eroding so the nav-mesh is pulled back from them like from any other wall.
+    if !blockers.is_empty() {
+        #[cfg(feature = "trace")]
+        let _span = info_span!("Mark blockers").entered();
+        mark_blockers(&mut open_tile, tile_coord, blockers, nav_mesh_settings);
+        open_tile.input_hash = None;
+    }
+
     // Remove areas that are too close to a wall.
     {
         #[cfg(feature = "trace")]
@@ -818,21 +2761,72 @@ pub fn build_tile_sync(
         erode_walkable_area(&mut open_tile, nav_mesh_settings);
     }
 
-    {
+    if nav_mesh_settings.median_area_filter {
         #[cfg(feature = "trace")]
-        let _span = info_span!("Calculate distance field").entered();
-        calculate_distance_field(&mut open_tile, nav_mesh_settings);
+        let _span = info_span!("Median filter areas").entered();
+        median_filter_areas(&mut open_tile, nav_mesh_settings);
     }
-    {
+
+    open_tile
+}
+
+fn build_tile_from_open_tile(
+    mut open_tile: OpenTile,
+    tile_coord: UVec2,
+    modifiers: &OpenTileModifiers,
+    nav_mesh_settings: &NavMeshSettings,
+) -> NavMeshTile {
+    if !modifiers.area_volumes.is_empty() {
+        #[cfg(feature = "trace")]
+        let _span = info_span!("Mark area volumes").entered();
+        mark_area_volumes(
+            &mut open_tile,
+            tile_coord,
+            &modifiers.area_volumes,
+            nav_mesh_settings,
+        );
+    }
+
+    if !modifiers.obstacles.is_empty() {
         #[cfg(feature = "trace")]
-        let _span = info_span!("Build regions").entered();
-        build_regions(&mut open_tile, nav_mesh_settings);
+        let _span = info_span!("Carve obstacles").entered();
+        carve_obstacles(
+            &mut open_tile,
+            tile_coord,
+            &modifiers.obstacles,
+            nav_mesh_settings,
+        );
+    }
+
+    match nav_mesh_settings.partitioning {
+        RegionPartitioning::Watershed => {
+            {
+                #[cfg(feature = "trace")]
+                let _span = info_span!("Calculate distance field").entered();
+                calculate_distance_field(&mut open_tile, nav_mesh_settings);
+            }
+            {
+                #[cfg(feature = "trace")]
+                let _span = info_span!("Build regions").entered();
+                build_regions(&mut open_tile, nav_mesh_settings);
+            }
+        }
+        RegionPartitioning::Monotone => {
+            #[cfg(feature = "trace")]
+            let _span = info_span!("Build regions (monotone)").entered();
+            build_regions_monotone(&mut open_tile, nav_mesh_settings);
+        }
+        RegionPartitioning::Layers => {
+            #[cfg(feature = "trace")]
+            let _span = info_span!("Build regions (layers)").entered();
+            build_layer_regions(&mut open_tile, nav_mesh_settings);
+        }
     }
 
     let contour_set = {
         #[cfg(feature = "trace")]
         let _span = info_span!("Build contours").entered();
-        build_contours(open_tile, nav_mesh_settings)
+        build_contours(&open_tile, nav_mesh_settings)
     };
 
     let poly_mesh = {
@@ -841,12 +2835,30 @@ pub fn build_tile_sync(
         build_poly_mesh(contour_set, nav_mesh_settings)
     };
 
-    {
+    let mut tile = {
         #[cfg(feature = "trace")]
         let _span = info_span!("Create nav-mesh tile from poly mesh").entered();
 
         create_nav_mesh_tile_from_poly_mesh(poly_mesh, tile_coord, nav_mesh_settings)
+    };
+    if modifiers.is_empty() {
+        tile.input_hash = open_tile.input_hash;
+    }
+
+    if let Some(jump_link_settings) = &nav_mesh_settings.jump_links {
+        #[cfg(feature = "trace")]
+        let _span = info_span!("Build jump links").entered();
+
+        build_jump_links(
+            &mut tile,
+            &open_tile,
+            tile_coord,
+            nav_mesh_settings,
+            jump_link_settings,
+        );
     }
+
+    tile
 }
 
 /*
@@ -0,0 +1,270 @@
+use std::collections::BTreeMap;
+
+use bevy::{
+    math::Vec3Swizzles,
+    prelude::{UVec2, Vec2, Vec3},
+};
+
+use crate::{
+    heightfields::{OpenSpan, OpenTile},
+    mesher::{EdgeConnection, VERTICES_IN_TRIANGLE},
+    tiles::{Link, NavMeshTile, OffMeshLink, OffMeshLinkKind, PolygonFilter},
+    JumpLinkSettings, NavMeshSettings,
+};
+
+/// Spacing in cell_width(s) between the points along a wall edge that are checked for links.
+const SAMPLE_SPACING: f32 = 4.0;
+
+/// Scans the wall edges of ``tile`` for ledges & gaps, adding an [OffMeshLink] for every landing found in ``open_tile``.
+///
+/// Landings in neighbouring tiles are skipped, links can't cross tile borders.
+pub fn build_jump_links(
+    tile: &mut NavMeshTile,
+    open_tile: &OpenTile,
+    tile_coord: UVec2,
+    nav_mesh_settings: &NavMeshSettings,
+    jump_link_settings: &JumpLinkSettings,
+) {
+    let tile_origin = nav_mesh_settings.get_tile_origin_with_border(tile_coord);
+    let sample_spacing = SAMPLE_SPACING * nav_mesh_settings.cell_width;
+    let height_range = f32::from(nav_mesh_settings.step_height + 1) * nav_mesh_settings.cell_height;
+    let max_climb_height =
+        f32::from(jump_link_settings.max_climb_height) * nav_mesh_settings.cell_height;
+
+    // Only the shortest link between two polygons is kept. Ordered so link indices are stable between builds.
+    let mut links = BTreeMap::new();
+    for (poly_index, polygon) in tile.polygons.iter().enumerate() {
+        let vertices = polygon.indices.map(|index| tile.vertices[index as usize]);
+        let centroid = (vertices[0] + vertices[1] + vertices[2]) / 3.0;
+
+        for (edge_index, edge) in tile.edges[poly_index].iter().enumerate() {
+            // Edges connected to other polygons or tiles aren't ledges.
+            if *edge != EdgeConnection::None {
+                continue;
+            }
+
+            let a = vertices[edge_index];
+            let b = vertices[(edge_index + 1) % VERTICES_IN_TRIANGLE];
+            let Some(edge_direction) = (b.xz() - a.xz()).try_normalize() else {
+                continue;
+            };
+
+            let mut outward = edge_direction.perp();
+            if outward.dot(a.xz().lerp(b.xz(), 0.5) - centroid.xz()) < 0.0 {
+                outward = -outward;
+            }
+
+            let samples = (a.xz().distance(b.xz()) / sample_spacing).floor().max(1.0);
+            for sample in 0..samples as u32 {
+                let start = a.lerp(b, (sample as f32 + 0.5) / samples);
+
+                let (drop_landing, jump_landing) = find_landings(
+                    open_tile,
+                    nav_mesh_settings,
+                    jump_link_settings,
+                    tile_origin,
+                    start,
+                    outward,
+                );
+
+                let landings = [
+                    drop_landing.map(|landing| (landing, OffMeshLinkKind::JumpDown)),
+                    jump_landing.map(|landing| (landing, OffMeshLinkKind::JumpAcross)),
+                ];
+                for (landing, kind) in landings.into_iter().flatten() {
+                    let Some((end_polygon, end)) = tile.find_polygon_at_point(
+                        landing,
+                        height_range,
+                        &PolygonFilter::default(),
+                    ) else {
+                        continue;
+                    };
+                    let start_polygon = poly_index as u16;
+                    if end_polygon == start_polygon {
+                        continue;
+                    }
+
+                    let (bidirectional, area) = match kind {
+                        OffMeshLinkKind::JumpDown => (
+                            start.y - end.y <= max_climb_height,
+                            jump_link_settings.jump_down_area,
+                        ),
+                        OffMeshLinkKind::JumpAcross => (true, jump_link_settings.jump_across_area),
+                    };
+
+                    let key = if bidirectional {
+                        (
+                            start_polygon.min(end_polygon),
+                            start_polygon.max(end_polygon),
+                            true,
+                        )
+                    } else {
+                        (start_polygon, end_polygon, false)
+                    };
+
+                    let link = OffMeshLink {
+                        start,
+                        end,
+                        start_polygon,
+                        end_polygon,
+                        bidirectional,
+                        area,
+                        kind,
+                    };
+                    let has_shorter = links.get(&key).is_some_and(|existing: &OffMeshLink| {
+                        existing.start.distance_squared(existing.end) <= start.distance_squared(end)
+                    });
+                    if !has_shorter {
+                        links.insert(key, link);
+                    }
+                }
+            }
+        }
+    }
+
+    let off_mesh_links = links.into_values().collect::<Box<[OffMeshLink]>>();
+    for (i, link) in off_mesh_links.iter().enumerate() {
+        tile.polygons[link.start_polygon as usize]
+            .links
+            .push(Link::OffMesh {
+                link: i as u16,
+                neighbour_polygon: link.end_polygon,
+                reverse: false,
+            });
+
+        if link.bidirectional {
+            tile.polygons[link.end_polygon as usize]
+                .links
+                .push(Link::OffMesh {
+                    link: i as u16,
+                    neighbour_polygon: link.start_polygon,
+                    reverse: true,
+                });
+        }
+    }
+    tile.off_mesh_links = off_mesh_links;
+}
+
+/// Walks outward from ``start`` one cell at a time, returning the landing spot of a drop & of a jump across a gap, if any.
+///
+/// Landings are only accepted on walkable spans with enough clearance for an agent coming from ``start``.
+fn find_landings(
+    open_tile: &OpenTile,
+    nav_mesh_settings: &NavMeshSettings,
+    jump_link_settings: &JumpLinkSettings,
+    tile_origin: Vec2,
+    start: Vec3,
+    outward: Vec2,
+) -> (Option<Vec3>, Option<Vec3>) {
+    let tile_side = nav_mesh_settings.get_tile_side_with_border();
+    let walkable_height = i64::from(nav_mesh_settings.walkable_height);
+    let step_height = i64::from(nav_mesh_settings.step_height);
+    let walkable_radius = u32::from(nav_mesh_settings.walkable_radius);
+    let max_drop_height = i64::from(jump_link_settings.max_drop_height);
+    let max_jump_distance = u32::from(jump_link_settings.max_jump_distance);
+
+    let height = ((start.y - nav_mesh_settings.world_bottom_bound) / nav_mesh_settings.cell_height)
+        .round() as i64;
+
+    // Returns the column ``step`` cells outward from start & the center of that column.
+    let column = |step: u32| {
+        let position = start.xz() + outward * (step as f32 * nav_mesh_settings.cell_width);
+        let cell = ((position - tile_origin) / nav_mesh_settings.cell_width).floor();
+        if cell.x < 0.0 || cell.y < 0.0 {
+            return None;
+        }
+
+        let (x, z) = (cell.x as usize, cell.y as usize);
+        if x >= tile_side || z >= tile_side {
+            return None;
+        }
+
+        let center = tile_origin + (cell + 0.5) * nav_mesh_settings.cell_width;
+        Some((&open_tile.cells[x + z * tile_side].spans, center))
+    };
+    let has_clearance = |span: &OpenSpan, top: i64| match span.max {
+        Some(max) => i64::from(max) >= top,
+        None => true,
+    };
+    let to_world = |center: Vec2, floor: u32| {
+        Vec3::new(
+            center.x,
+            nav_mesh_settings.world_bottom_bound + floor as f32 * nav_mesh_settings.cell_height,
+            center.y,
+        )
+    };
+
+    // Eroded ground next to the ledge, the gap itself & eroded ground on the far side.
+    let max_steps = walkable_radius * 2 + max_jump_distance + 1;
+
+    let mut drop_landing = None;
+    let mut gap_cells = 0;
+    for step in 1..=max_steps {
+        let Some((spans, center)) = column(step) else {
+            break;
+        };
+
+        let level_span = spans.iter().find(|span| {
+            (i64::from(span.min) - height).abs() <= step_height
+                && has_clearance(span, i64::from(span.min) + walkable_height)
+        });
+        if let Some(span) = level_span {
+            if gap_cells > 0 && open_tile.areas[span.tile_index].is_some() {
+                return (drop_landing, Some(to_world(center, span.min)));
+            }
+
+            // Still on the (eroded) ground around the edge.
+            continue;
+        }
+
+        // Anything sticking up into the agent's path blocks both drops & jumps.
+        let is_open = spans.is_empty()
+            || spans.iter().any(|span| {
+                i64::from(span.min) < height && has_clearance(span, height + walkable_height)
+            });
+        if !is_open {
+            break;
+        }
+
+        gap_cells += 1;
+        if gap_cells == 1 {
+            // Highest floor below the ledge we can drop down to.
+            let drop_floor = spans
+                .iter()
+                .filter(|span| {
+                    let drop = height - i64::from(span.min);
+                    drop > step_height
+                        && drop <= max_drop_height
+                        && has_clearance(span, height + walkable_height)
+                })
+                .map(|span| span.min)
+                .max();
+
+            if let Some(drop_floor) = drop_floor {
+                // The ground at the base of the ledge is eroded, keep going until we find walkable ground.
+                for landing_step in step..=step + walkable_radius * 2 + 1 {
+                    let Some((spans, center)) = column(landing_step) else {
+                        break;
+                    };
+                    let Some(span) = spans.iter().find(|span| {
+                        (i64::from(span.min) - i64::from(drop_floor)).abs() <= step_height
+                            && has_clearance(span, i64::from(span.min) + walkable_height)
+                    }) else {
+                        break;
+                    };
+
+                    if open_tile.areas[span.tile_index].is_some() {
+                        drop_landing = Some(to_world(center, span.min));
+                        break;
+                    }
+                }
+            }
+        }
+
+        if gap_cells > max_jump_distance {
+            break;
+        }
+    }
+
+    (drop_landing, None)
+}
@@ -1,9 +1,9 @@
 //! Module for querying the nav-mesh.
 
-use bevy::prelude::{UVec2, Vec3};
+use bevy::prelude::{UVec2, Vec3, Vec3Swizzles};
 
 use crate::{
-    tiles::{Link, NavMeshTiles},
+    tiles::{Link, NavMeshTiles, PolyRef, PolygonFilter},
     NavMeshSettings,
 };
 
@@ -33,21 +33,26 @@ struct NavMeshNode {
 pub enum FindPolygonPathError {
     /// Nav-mesh couldn't be retrieved from lock.
     NavMeshUnavailable,
-    /// No polygon found near ``start_pos``.
+    /// ``start_pos`` is outside [NavMeshSettings::world_bounds].
+    StartOutOfWorldBounds,
+    /// ``end_pos`` is outside [NavMeshSettings::world_bounds].
+    EndOutOfWorldBounds,
+    /// No polygon found near ``start_pos``, or the ``start_ref`` is stale or doesn't pass the polygon filter.
     NoValidStartPolygon,
-    /// No polygon found near ``end_pos``.
+    /// No polygon found near ``end_pos``, or the ``end_ref`` is stale or doesn't pass the polygon filter.
     NoValidEndPolygon,
 }
 
 /// Performs A* pathfinding on the supplied nav-mesh.
-/// Returning the polygons crossed as a [Vec] containing the tile coordinate ([UVec2]) & polygon index ([u16]) or [FindPathError]
+/// Returning the polygons crossed as a [Vec] of [PolyRef] or [FindPathError]
 ///
 /// * ``nav_mesh`` - Nav-mesh to pathfind across.
 /// * ``nav_mesh_settings`` - Nav-mesh settings used to generate ``nav_mesh``.
-/// * ``start_pos`` - Starting position for the path.
-/// * ``end_pos`` - Destination position for the path, i.e where you want to go.
+/// * ``start_pos`` - Starting position for the path. Must be within [NavMeshSettings::world_bounds].
+/// * ``end_pos`` - Destination position for the path, i.e where you want to go. Must be within [NavMeshSettings::world_bounds].
 /// * ``position_search_radius`` - Radius to search for a start & end polygon in. In world units. If **``None``** is supplied a default value of ``5.0`` is used.
 /// * ``area_cost_multipliers`` - Multipliers for area cost, use to prioritize or deprioritize taking certain paths. Values not present default to 1.0. Lesser value means the path costs less.
+/// * ``polygon_filter`` - Filter for which polygons may be crossed based on their flags. If **``None``** is supplied [PolygonFilter::default] is used.
 pub fn find_polygon_path(
     nav_mesh: &NavMeshTiles,
     nav_mesh_settings: &NavMeshSettings,
@@ -55,23 +60,85 @@ pub fn find_polygon_path(
     end_pos: Vec3,
     position_search_radius: Option<f32>,
     area_cost_multipliers: Option<&[f32]>, // TODO: A slice might not be the best choice when there are many area types.
-) -> Result<Vec<(UVec2, u16)>, FindPolygonPathError> {
+    polygon_filter: Option<PolygonFilter>,
+) -> Result<Vec<PolyRef>, FindPolygonPathError> {
+    if !nav_mesh_settings.is_within_world_bounds(start_pos.xz()) {
+        return Err(FindPolygonPathError::StartOutOfWorldBounds);
+    }
+    if !nav_mesh_settings.is_within_world_bounds(end_pos.xz()) {
+        return Err(FindPolygonPathError::EndOutOfWorldBounds);
+    }
+
     let search_radius = position_search_radius.unwrap_or(5.0);
 
-    let Some((start_tile, start_poly, start_pos)) =
-        nav_mesh.find_closest_polygon_in_box(nav_mesh_settings, start_pos, search_radius)
-    else {
+    let Some((start_ref, start_pos)) = nav_mesh.find_closest_polygon_in_box(
+        nav_mesh_settings,
+        start_pos,
+        search_radius,
+        polygon_filter,
+    ) else {
         return Err(FindPolygonPathError::NoValidStartPolygon);
     };
 
-    let Some((end_tile, end_poly, end_pos)) =
-        nav_mesh.find_closest_polygon_in_box(nav_mesh_settings, end_pos, search_radius)
-    else {
+    let Some((end_ref, end_pos)) = nav_mesh.find_closest_polygon_in_box(
+        nav_mesh_settings,
+        end_pos,
+        search_radius,
+        polygon_filter,
+    ) else {
         return Err(FindPolygonPathError::NoValidEndPolygon);
     };
 
-    if start_tile == end_tile && start_poly == end_poly {
-        return Ok(vec![(start_tile, start_poly)]);
+    find_polygon_path_between_refs(
+        nav_mesh,
+        start_ref,
+        start_pos,
+        end_ref,
+        end_pos,
+        area_cost_multipliers,
+        polygon_filter,
+    )
+}
+
+/// Performs A* pathfinding between two already known polygons.
+/// Returning the polygons crossed as a [Vec] of [PolyRef] or [FindPathError]
+///
+/// * ``nav_mesh`` - Nav-mesh to pathfind across.
+/// * ``start_ref`` - Polygon containing ``start_pos``.
+/// * ``start_pos`` - Starting position for the path.
+/// * ``end_ref`` - Polygon containing ``end_pos``.
+/// * ``end_pos`` - Destination position for the path, i.e where you want to go.
+/// * ``area_cost_multipliers`` - Multipliers for area cost, use to prioritize or deprioritize taking certain paths. Values not present default to 1.0. Lesser value means the path costs less.
+/// * ``polygon_filter`` - Filter for which polygons may be crossed based on their flags. If **``None``** is supplied [PolygonFilter::default] is used.
+pub fn find_polygon_path_between_refs(
+    nav_mesh: &NavMeshTiles,
+    start_ref: PolyRef,
+    start_pos: Vec3,
+    end_ref: PolyRef,
+    end_pos: Vec3,
+    area_cost_multipliers: Option<&[f32]>,
+    polygon_filter: Option<PolygonFilter>,
+) -> Result<Vec<PolyRef>, FindPolygonPathError> {
+    let polygon_filter = polygon_filter.unwrap_or_default();
+
+    if !nav_mesh
+        .get_tile_and_polygon_by_ref(start_ref)
+        .is_some_and(|(_, polygon)| polygon_filter.passes(polygon.flags))
+    {
+        return Err(FindPolygonPathError::NoValidStartPolygon);
+    }
+    if !nav_mesh
+        .get_tile_and_polygon_by_ref(end_ref)
+        .is_some_and(|(_, polygon)| polygon_filter.passes(polygon.flags))
+    {
+        return Err(FindPolygonPathError::NoValidEndPolygon);
+    }
+
+    let (start_tile, start_poly) = (start_ref.tile(), start_ref.polygon());
+    let (end_tile, end_poly) = (end_ref.tile(), end_ref.polygon());
+
+    if start_ref == end_ref {
+        return Ok(vec![start_ref]);
     }
 
     let mut nodes = Vec::with_capacity(10);
@@ -126,8 +193,21 @@ pub fn find_polygon_path(
                     direction,
                     ..
                 } => (direction.offset(best_tile), *neighbour_polygon),
+                Link::OffMesh {
+                    neighbour_polygon, ..
+                } => (best_tile, *neighbour_polygon),
             };
 
+            // Skip polygons the filter doesn't allow.
+            let link_polygon_flags = nav_mesh
+                .tiles
+                .get(&link_tile)
+                .and_then(|tile| tile.polygons.get(link_polygon as usize))
+                .map(|polygon| polygon.flags);
+            if !link_polygon_flags.is_some_and(|flags| polygon_filter.passes(flags)) {
+                continue;
+            }
+
             // Don't go back to our parent.
             if let Some(parent) = best_parent {
                 if nodes[parent].tile == link_tile && nodes[parent].polygon == link_polygon {
@@ -172,6 +252,12 @@ pub fn find_polygon_path(
 
                         clamped_a.lerp(clamped_b, 0.5)
                     }
+                    Link::OffMesh { link, reverse, .. } => {
+                        // Where the link lands.
+                        node_tile.off_mesh_links[*link as usize]
+                            .end_points(*reverse)
+                            .1
+                    }
                 };
 
                 nodes.push(NavMeshNode {
@@ -199,19 +285,32 @@ pub fn find_polygon_path(
                     *multipliers.get(area.0 as usize).unwrap_or(&1.0)
                 });
 
+                let current_cost = match link {
+                    Link::OffMesh { link, reverse, .. } => {
+                        // Walk to the start of the link, then traverse the link at the cost of its own area.
+                        let off_mesh_link = &node_tile.off_mesh_links[*link as usize];
+                        let (link_start, _) = off_mesh_link.end_points(*reverse);
+                        let link_cost_multiplier =
+                            area_cost_multipliers.map_or(1.0, |multipliers| {
+                                *multipliers
+                                    .get(off_mesh_link.area.0 as usize)
+                                    .unwrap_or(&1.0)
+                            });
+
+                        best_position.distance(link_start) * node_cost_multiplier
+                            + link_start.distance(neighbour_node.position) * link_cost_multiplier
+                    }
+                    _ => best_position.distance(neighbour_node.position) * node_cost_multiplier,
+                };
+
                 let (cost, heuristic) = if end_tile == link_tile && end_poly == link_polygon {
                     // Special case for the final node.
-                    let current_cost =
-                        best_position.distance(neighbour_node.position) * node_cost_multiplier;
                     let end_cost = neighbour_node.position.distance(end_pos);
 
                     let cost = best_cost + current_cost + end_cost;
 
                     (cost, 0.0)
                 } else {
-                    let current_cost =
-                        best_position.distance(neighbour_node.position) * node_cost_multiplier;
-
                     let cost = best_cost + current_cost;
                     let heuristic = neighbour_node.position.distance(end_pos) * HEURISTIC_SCALE;
 
@@ -280,7 +379,11 @@ pub fn find_polygon_path(
     while let Some(parent_index) = parent {
         let node = &nodes[parent_index];
 
-        path.push((node.tile, node.polygon));
+        // Every node was reached through a link in the borrowed nav-mesh so the polygon must exist.
+        let poly_ref = nav_mesh
+            .get_poly_ref(node.tile, node.polygon)
+            .expect("Path node's polygon is missing from the nav-mesh.");
+        path.push(poly_ref);
 
         parent = node.parent;
     }
@@ -297,6 +400,8 @@ pub enum StringPullingError {
     MissingEndTile,
     MissingNodeTile,
     NoLinkBetweenPathPoints,
+    /// A [PolyRef] in the path refers to a tile that has since been regenerated or removed.
+    StalePolyRef,
 }
 
 /// Performs "string pulling" on a path of polygons. Used to convert [find_path]'s result to a world space path.
@@ -306,27 +411,105 @@ pub fn perform_string_pulling_on_path(
     nav_mesh: &NavMeshTiles,
     start_pos: Vec3,
     end_pos: Vec3,
-    path: &[(UVec2, u16)],
+    path: &[PolyRef],
 ) -> Result<Vec<Vec3>, StringPullingError> {
     if path.is_empty() {
         return Err(StringPullingError::PathEmpty);
     }
 
-    let Some(start_tile) = nav_mesh.tiles.get(&path[0].0) else {
+    if !path
+        .iter()
+        .all(|poly_ref| nav_mesh.is_valid_poly_ref(*poly_ref))
+    {
+        return Err(StringPullingError::StalePolyRef);
+    }
+
+    let Some((start_tile, start_polygon)) = nav_mesh.get_tile_and_polygon_by_ref(path[0]) else {
         return Err(StringPullingError::MissingStartTile);
     };
-    let Some(end_tile) = nav_mesh.tiles.get(&path.last().unwrap().0) else {
+    let Some((end_tile, end_polygon)) = nav_mesh.get_tile_and_polygon_by_ref(*path.last().unwrap())
+    else {
         return Err(StringPullingError::MissingEndTile);
     };
 
-    let start_pos = start_tile
-        .get_closest_point_in_polygon(&start_tile.polygons[path[0].1 as usize], start_pos);
-    let end_pos = end_tile
-        .get_closest_point_in_polygon(&end_tile.polygons[path.last().unwrap().1 as usize], end_pos);
+    let start_pos = start_tile.get_closest_point_in_polygon(start_polygon, start_pos);
+    let end_pos = end_tile.get_closest_point_in_polygon(end_polygon, end_pos);
 
     let mut string_path = Vec::with_capacity(path.len() / 3 + 2);
     string_path.push(start_pos);
 
+    // Off-mesh links can't be string pulled across, split the path at them & pull each part on its own.
+    let mut segment_start = 0;
+    let mut segment_start_pos = start_pos;
+    for i in 0..path.len() - 1 {
+        let Some((link_start, link_end)) = find_off_mesh_link(nav_mesh, path[i], path[i + 1])
+        else {
+            continue;
+        };
+
+        string_pull_segment(
+            nav_mesh,
+            segment_start_pos,
+            link_start,
+            &path[segment_start..=i],
+            &mut string_path,
+        )?;
+        string_path.push(link_end);
+
+        segment_start = i + 1;
+        segment_start_pos = link_end;
+    }
+
+    string_pull_segment(
+        nav_mesh,
+        segment_start_pos,
+        end_pos,
+        &path[segment_start..],
+        &mut string_path,
+    )?;
+
+    Ok(string_path)
+}
+
+/// Returns the end points of the off-mesh link taken from ``current`` to ``next`` if they aren't connected by an edge.
+fn find_off_mesh_link(
+    nav_mesh: &NavMeshTiles,
+    current: PolyRef,
+    next: PolyRef,
+) -> Option<(Vec3, Vec3)> {
+    if current.tile() != next.tile() {
+        return None;
+    }
+
+    let tile = nav_mesh.tiles.get(&current.tile())?;
+    let links = &tile.polygons[current.polygon() as usize].links;
+    let has_edge_link = links.iter().any(|link| {
+        matches!(link, Link::Internal { neighbour_polygon, .. } if *neighbour_polygon == next.polygon())
+    });
+    if has_edge_link {
+        return None;
+    }
+
+    links.iter().find_map(|link| match link {
+        Link::OffMesh {
+            link,
+            neighbour_polygon,
+            reverse,
+        } if *neighbour_polygon == next.polygon() => {
+            Some(tile.off_mesh_links[*link as usize].end_points(*reverse))
+        }
+        _ => None,
+    })
+}
+
+/// String pulls a part of a path without any off-mesh links from ``start_pos`` to ``end_pos``, appending the result to ``string_path``.
+fn string_pull_segment(
+    nav_mesh: &NavMeshTiles,
+    start_pos: Vec3,
+    end_pos: Vec3,
+    path: &[PolyRef],
+    string_path: &mut Vec<Vec3>,
+) -> Result<(), StringPullingError> {
     if path.len() > 1 {
         let mut portal_apex = start_pos;
         let mut portal_left = start_pos;
@@ -340,11 +523,11 @@ pub fn perform_string_pulling_on_path(
             let (left, right) = if let Some(next) = path.get(i + 1) {
                 let current = &path[i];
                 // Find link between this and next in path.
-                let Some(node_tile) = nav_mesh.tiles.get(&current.0) else {
+                let Some(node_tile) = nav_mesh.tiles.get(&current.tile()) else {
                     return Err(StringPullingError::MissingNodeTile);
                 };
-                let is_internal = current.0 == next.0;
-                let Some(link) = node_tile.polygons[current.1 as usize]
+                let is_internal = current.tile() == next.tile();
+                let Some(link) = node_tile.polygons[current.polygon() as usize]
                     .links
                     .iter()
                     .find(|link| {
@@ -352,22 +535,23 @@ pub fn perform_string_pulling_on_path(
                         match link {
                             Link::Internal {
                                 neighbour_polygon, ..
-                            } => is_internal && next.1 == *neighbour_polygon,
+                            } => is_internal && next.polygon() == *neighbour_polygon,
                             Link::External {
                                 neighbour_polygon,
                                 direction,
                                 ..
                             } => {
-                                direction.offset(current.0) == next.0
-                                    && next.1 == *neighbour_polygon
+                                direction.offset(current.tile()) == next.tile()
+                                    && next.polygon() == *neighbour_polygon
                             }
+                            Link::OffMesh { .. } => false,
                         }
                     })
                 else {
                     return Err(StringPullingError::NoLinkBetweenPathPoints);
                 };
 
-                let indices = &node_tile.polygons[current.1 as usize].indices;
+                let indices = &node_tile.polygons[current.polygon() as usize].indices;
                 match link {
                     Link::Internal { edge, .. } => {
                         let a = node_tile.vertices[indices[*edge as usize] as usize];
@@ -392,6 +576,7 @@ pub fn perform_string_pulling_on_path(
 
                         (clamped_a, clamped_b)
                     }
+                    Link::OffMesh { .. } => unreachable!(),
                 }
             } else {
                 (end_pos, end_pos)
@@ -451,7 +636,7 @@ pub fn perform_string_pulling_on_path(
         string_path.push(end_pos);
     }
 
-    Ok(string_path)
+    Ok(())
 }
 
 #[derive(Debug)]
@@ -465,10 +650,11 @@ pub enum FindPathError {
 ///
 /// * ``nav_mesh`` - Nav-mesh to pathfind across.
 /// * ``nav_mesh_settings`` - Nav-mesh settings used to generate ``nav_mesh``.
-/// * ``start_pos`` - Starting position for the path.
-/// * ``end_pos`` - Destination position for the path, i.e where you want to go.
+/// * ``start_pos`` - Starting position for the path. Must be within [NavMeshSettings::world_bounds].
+/// * ``end_pos`` - Destination position for the path, i.e where you want to go. Must be within [NavMeshSettings::world_bounds].
 /// * ``position_search_radius`` - Radius to search for a start & end polygon in. In world units. If **``None``** is supplied a default value of ``5.0`` is used.
 /// * ``area_cost_multipliers`` - Multipliers for area cost, use to prioritize or deprioritize taking certain paths. Values not present default to 1.0. Lesser value means the path costs less.
+/// * ``polygon_filter`` - Filter for which polygons may be crossed based on their flags. If **``None``** is supplied [PolygonFilter::default] is used.
 pub fn find_path(
     nav_mesh: &NavMeshTiles,
     nav_mesh_settings: &NavMeshSettings,
@@ -476,6 +662,7 @@ pub fn find_path(
     end_pos: Vec3,
     position_search_radius: Option<f32>,
     area_cost_multipliers: Option<&[f32]>,
+    polygon_filter: Option<PolygonFilter>,
 ) -> Result<Vec<Vec3>, FindPathError> {
     match find_polygon_path(
         nav_mesh,
@@ -484,6 +671,7 @@ pub fn find_path(
         end_pos,
         position_search_radius,
         area_cost_multipliers,
+        polygon_filter,
     ) {
         Ok(path) => perform_string_pulling_on_path(nav_mesh, start_pos, end_pos, &path)
             .map_err(FindPathError::StringPulling),
@@ -13,7 +13,8 @@ use crate::{
 use super::mesher::PolyMesh;
 
 /// Representation of a link between different polygons either internal to the tile or external (crossing over to another tile).
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum Link {
     Internal {
         /// Edge on self polygon.
@@ -33,27 +34,275 @@ pub enum Link {
         // MAx % of this edge that connects to the linked polygon.
         bound_max: u8, // For example: 10% -> 50% = the connected edge covers 10% from vertex A to B to 50%.
     },
+    /// Link through one of the tile's [OffMeshLink]s.
+    OffMesh {
+        /// Index into [NavMeshTile::off_mesh_links].
+        link: u16,
+        /// Index of polygon this polygon is linked to.
+        neighbour_polygon: u16,
+        /// True if the link is traversed from its end to its start.
+        reverse: bool,
+    },
+}
+
+/// What kind of traversal an [OffMeshLink] represents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum OffMeshLinkKind {
+    /// Dropping down from a ledge.
+    JumpDown,
+    /// Leaping over a gap onto a surface at roughly the same height.
+    JumpAcross,
+}
+
+/// A connection between two polygons which aren't connected by an edge, such as a ledge that can be dropped from.
+///
+/// Both ends are always within the same tile.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct OffMeshLink {
+    /// Start of the link in world space, on ``start_polygon``.
+    pub start: Vec3,
+    /// End of the link in world space, on ``end_polygon``.
+    pub end: Vec3,
+    pub start_polygon: u16,
+    pub end_polygon: u16,
+    /// If true the link can also be traversed from ``end`` to ``start``.
+    pub bidirectional: bool,
+    /// Area of the link itself. Used by ``area_cost_multipliers`` when pathfinding.
+    pub area: Area,
+    pub kind: OffMeshLinkKind,
+}
+impl OffMeshLink {
+    /// Returns the point the link is entered from & the point it lands on.
+    #[inline]
+    pub fn end_points(&self, reverse: bool) -> (Vec3, Vec3) {
+        if reverse {
+            (self.end, self.start)
+        } else {
+            (self.start, self.end)
+        }
+    }
 }
 
+/// Flags every polygon is given when its tile is generated.
+pub const DEFAULT_POLYGON_FLAGS: u16 = 1 << 0;
+/// Flag marking a polygon as temporarily impassable, for example by a closed [crate::NavMeshDoor]. Excluded by [PolygonFilter::default].
+pub const DISABLED_POLYGON_FLAG: u16 = 1 << 15;
+
 /// A polygon within a nav-mesh tile.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct Polygon {
     pub indices: [u32; VERTICES_IN_TRIANGLE],
     pub links: SmallVec<[Link; VERTICES_IN_TRIANGLE]>, // This becomes a mess memory wise with a ton of different small objects around.
     pub area: Area,
+    /// User defined flags which can be changed at runtime without regenerating the tile. Queries use these together with a [PolygonFilter].
+    ///
+    /// Reset to [DEFAULT_POLYGON_FLAGS] whenever the tile is regenerated.
+    pub flags: u16,
+}
+
+/// Filter deciding which polygons queries are allowed to use based on [Polygon::flags].
+///
+/// A polygon passes if it has at least one flag in ``include_flags`` & none in ``exclude_flags``.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PolygonFilter {
+    pub include_flags: u16,
+    pub exclude_flags: u16,
+}
+impl Default for PolygonFilter {
+    fn default() -> Self {
+        Self {
+            include_flags: u16::MAX,
+            exclude_flags: DISABLED_POLYGON_FLAG,
+        }
+    }
+}
+impl PolygonFilter {
+    /// Returns true if a polygon with ``flags`` passes the filter.
+    #[inline]
+    pub fn passes(&self, flags: u16) -> bool {
+        (flags & self.include_flags) != 0 && (flags & self.exclude_flags) == 0
+    }
 }
 
 /*
 *   Polygons make up a form of graph, linking to other polygons (which could be on another mesh)
 */
 
+/// Node in a [PolygonBvh].
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+struct BvhNode {
+    min: Vec3,
+    max: Vec3,
+    /// Polygon index if positive or zero (leaf node), negative escape offset to the next sibling otherwise.
+    index: i32,
+}
+
+/// Bounding volume hierarchy over the polygons of a [NavMeshTile].
+///
+/// Nodes are stored depth-first, skipping a subtree is done by jumping ahead by the node's escape offset.
+#[derive(Default, Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct PolygonBvh {
+    nodes: Box<[BvhNode]>,
+}
+impl PolygonBvh {
+    fn build(vertices: &[Vec3], polygons: &[Polygon]) -> Self {
+        let mut items: Vec<BvhNode> = polygons
+            .iter()
+            .enumerate()
+            .map(|(i, polygon)| {
+                let mut min = Vec3::INFINITY;
+                let mut max = Vec3::NEG_INFINITY;
+                for index in polygon.indices {
+                    let vertex = vertices[index as usize];
+                    min = min.min(vertex);
+                    max = max.max(vertex);
+                }
+
+                BvhNode {
+                    min,
+                    max,
+                    index: i as i32,
+                }
+            })
+            .collect();
+
+        let mut nodes = Vec::with_capacity(items.len() * 2);
+        if !items.is_empty() {
+            subdivide_bvh(&mut items, &mut nodes);
+        }
+
+        Self {
+            nodes: nodes.into_boxed_slice(),
+        }
+    }
+
+    /// Calls ``callback`` with the index of every polygon whose bounds overlap the box between ``min`` & ``max``.
+    pub fn query_aabb(&self, min: Vec3, max: Vec3, mut callback: impl FnMut(u16)) {
+        let mut i = 0;
+        while i < self.nodes.len() {
+            let node = &self.nodes[i];
+            let overlap = node.min.cmple(max).all() && node.max.cmpge(min).all();
+            let is_leaf = node.index >= 0;
+
+            if is_leaf && overlap {
+                callback(node.index as u16);
+            }
+
+            if overlap || is_leaf {
+                i += 1;
+            } else {
+                i += (-node.index) as usize;
+            }
+        }
+    }
+
+    /// Calls ``callback`` with the index of every polygon whose bounds are crossed by the segment from ``origin`` to ``origin + direction * max_distance``.
+    ///
+    /// ``direction`` is expected to be normalized.
+    pub fn query_ray(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        max_distance: f32,
+        mut callback: impl FnMut(u16),
+    ) {
+        let inverse_direction = direction.recip();
+
+        let mut i = 0;
+        while i < self.nodes.len() {
+            let node = &self.nodes[i];
+            let overlap =
+                ray_intersects_aabb(origin, inverse_direction, max_distance, node.min, node.max);
+            let is_leaf = node.index >= 0;
+
+            if is_leaf && overlap {
+                callback(node.index as u16);
+            }
+
+            if overlap || is_leaf {
+                i += 1;
+            } else {
+                i += (-node.index) as usize;
+            }
+        }
+    }
+}
+
+fn subdivide_bvh(items: &mut [BvhNode], nodes: &mut Vec<BvhNode>) {
+    let node_index = nodes.len();
+
+    if items.len() == 1 {
+        nodes.push(items[0]);
+        return;
+    }
+
+    let mut min = Vec3::INFINITY;
+    let mut max = Vec3::NEG_INFINITY;
+    for item in items.iter() {
+        min = min.min(item.min);
+        max = max.max(item.max);
+    }
+    nodes.push(BvhNode { min, max, index: 0 });
+
+    // Split along the longest axis.
+    let extents = max - min;
+    let axis = if extents.x >= extents.y && extents.x >= extents.z {
+        0
+    } else if extents.y >= extents.z {
+        1
+    } else {
+        2
+    };
+    items.sort_unstable_by(|a, b| {
+        (a.min[axis] + a.max[axis]).total_cmp(&(b.min[axis] + b.max[axis]))
+    });
+
+    let (left, right) = items.split_at_mut(items.len() / 2);
+    subdivide_bvh(left, nodes);
+    subdivide_bvh(right, nodes);
+
+    let escape = nodes.len() - node_index;
+    nodes[node_index].index = -(escape as i32);
+}
+
+fn ray_intersects_aabb(
+    origin: Vec3,
+    inverse_direction: Vec3,
+    max_distance: f32,
+    min: Vec3,
+    max: Vec3,
+) -> bool {
+    let t_a = (min - origin) * inverse_direction;
+    let t_b = (max - origin) * inverse_direction;
+
+    // NaN happens when the ray is parallel to & on an axis plane. min/max_element ignore it by picking the other operand.
+    let t_min = t_a.min(t_b).max_element().max(0.0);
+    let t_max = t_a.max(t_b).min_element().min(max_distance);
+
+    t_min <= t_max
+}
+
 /// A single nav-mesh tile.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct NavMeshTile {
     /// Vertices in world space.
     pub vertices: Box<[Vec3]>,
     pub polygons: Box<[Polygon]>,
     pub edges: Box<[[EdgeConnection; VERTICES_IN_TRIANGLE]]>,
+    /// Spatial index over ``polygons``. Used to speed up point, box & ray queries.
+    pub bvh: PolygonBvh,
+    /// Jump links generated for this tile. Referenced by [Link::OffMesh].
+    pub off_mesh_links: Box<[OffMeshLink]>,
+    /// Hash of the geometry the tile was generated from. Used to skip regenerating tiles loaded from a [crate::bake::BakedNavMesh] whose geometry hasn't changed.
+    ///
+    /// ``None`` if obstacles, area volumes or blockers affected the tile.
+    pub input_hash: Option<u64>,
 }
 impl NavMeshTile {
     /// Returns the closest point on ``polygon`` to ``position``.
@@ -66,6 +315,145 @@ impl NavMeshTile {
 
         closest_point_on_edges(&vertices, position)
     }
+
+    /// Returns the distance along ``direction`` at which the segment from ``origin`` to ``origin + direction * max_distance`` crosses ``polygon``, if it does.
+    pub fn raycast_polygon(
+        &self,
+        polygon: &Polygon,
+        origin: Vec3,
+        direction: Vec3,
+        max_distance: f32,
+    ) -> Option<f32> {
+        let [a, b, c] = polygon.indices.map(|index| self.vertices[index as usize]);
+
+        // Möller–Trumbore, both sides of the triangle count.
+        let ab = b - a;
+        let ac = c - a;
+        let p = direction.cross(ac);
+        let determinant = ab.dot(p);
+        if determinant.abs() < f32::EPSILON {
+            return None;
+        }
+        let inverse_determinant = 1.0 / determinant;
+
+        let to_origin = origin - a;
+        let u = to_origin.dot(p) * inverse_determinant;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+
+        let q = to_origin.cross(ab);
+        let v = direction.dot(q) * inverse_determinant;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+
+        let distance = ac.dot(q) * inverse_determinant;
+
+        (0.0..=max_distance).contains(&distance).then_some(distance)
+    }
+
+    /// Returns the index of the polygon directly below or above ``position`` within ``height_range`` & the position on it.
+    ///
+    /// If several polygons overlap ``position`` on the XZ-plane the one closest vertically is returned. Polygons not passing ``filter`` are ignored.
+    pub(super) fn find_polygon_at_point(
+        &self,
+        position: Vec3,
+        height_range: f32,
+        filter: &PolygonFilter,
+    ) -> Option<(u16, Vec3)> {
+        let min = position - Vec3::Y * height_range;
+        let max = position + Vec3::Y * height_range;
+
+        let mut out_polygon = None;
+        let mut out_distance = f32::INFINITY;
+        self.bvh.query_aabb(min, max, |poly_i| {
+            let polygon = &self.polygons[poly_i as usize];
+            if !filter.passes(polygon.flags) {
+                return;
+            }
+            let vertices = polygon.indices.map(|index| self.vertices[index as usize]);
+
+            let Some(height) = get_height_in_triangle(&vertices, position) else {
+                return;
+            };
+
+            let distance = (height - position.y).abs();
+            if distance <= height_range && distance < out_distance {
+                out_distance = distance;
+                out_polygon = Some((poly_i, Vec3::new(position.x, height, position.z)));
+            }
+        });
+
+        out_polygon
+    }
+}
+
+/// Stable handle to a polygon in the nav-mesh.
+///
+/// Combines the tile coordinate & polygon index with a salt taken from the tile's generation. When a tile is regenerated or removed any existing handle into it becomes stale, which can be checked with [NavMeshTiles::is_valid_poly_ref].
+///
+/// Packed into a [u64] as 16 bits each of tile X, tile Y, salt & polygon index (from most to least significant), so tile coordinates must fit in a [u16].
+/// Use [PolyRef::to_bits] & [PolyRef::from_bits] to store handles outside the nav-mesh.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PolyRef(u64);
+impl PolyRef {
+    const BITS: u32 = 16;
+    const MASK: u64 = (1 << Self::BITS) - 1;
+
+    #[inline]
+    fn new(tile: UVec2, salt: u16, polygon: u16) -> Self {
+        debug_assert!(
+            tile.cmple(UVec2::splat(u16::MAX.into())).all(),
+            "Tile coordinate {tile} doesn't fit in a PolyRef."
+        );
+
+        Self(
+            (u64::from(tile.x) & Self::MASK) << (Self::BITS * 3)
+                | (u64::from(tile.y) & Self::MASK) << (Self::BITS * 2)
+                | u64::from(salt) << Self::BITS
+                | u64::from(polygon),
+        )
+    }
+
+    /// Returns the coordinate of the tile containing the polygon.
+    #[inline]
+    pub fn tile(&self) -> UVec2 {
+        UVec2::new(
+            (self.0 >> (Self::BITS * 3)) as u32,
+            ((self.0 >> (Self::BITS * 2)) & Self::MASK) as u32,
+        )
+    }
+    /// Returns the index of the polygon within its tile.
+    #[inline]
+    pub fn polygon(&self) -> u16 {
+        (self.0 & Self::MASK) as u16
+    }
+    /// Returns the salt of the tile generation this handle was created from.
+    #[inline]
+    pub fn salt(&self) -> u16 {
+        ((self.0 >> Self::BITS) & Self::MASK) as u16
+    }
+
+    /// Returns the packed representation of the handle.
+    #[inline]
+    pub fn to_bits(self) -> u64 {
+        self.0
+    }
+    /// Creates a handle from the packed representation returned by [PolyRef::to_bits].
+    #[inline]
+    pub fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+}
+impl std::fmt::Debug for PolyRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PolyRef")
+            .field("tile", &self.tile())
+            .field("salt", &self.salt())
+            .field("polygon", &self.polygon())
+            .finish()
+    }
 }
 
 /// Container for all nav-mesh tiles. Used for pathfinding queries.
@@ -83,6 +471,50 @@ impl NavMeshTiles {
         &self.tiles
     }
 
+    /// Returns the salt for the current generation of ``tile_coord``.
+    #[inline]
+    fn get_tile_salt(&self, tile_coord: UVec2) -> u16 {
+        // Generations come from a global ticker, truncating only repeats a salt after 2^16 tile generations.
+        self.tile_generations.get(&tile_coord).copied().unwrap_or(0) as u16
+    }
+
+    /// Returns a [PolyRef] to ``polygon`` in ``tile_coord`` if that polygon currently exists.
+    pub fn get_poly_ref(&self, tile_coord: UVec2, polygon: u16) -> Option<PolyRef> {
+        let tile = self.tiles.get(&tile_coord)?;
+        if polygon as usize >= tile.polygons.len() {
+            return None;
+        }
+
+        Some(PolyRef::new(
+            tile_coord,
+            self.get_tile_salt(tile_coord),
+            polygon,
+        ))
+    }
+
+    /// Returns true if ``poly_ref`` still refers to the polygon it was created for.
+    ///
+    /// Handles become stale when their tile is regenerated or removed.
+    pub fn is_valid_poly_ref(&self, poly_ref: PolyRef) -> bool {
+        self.get_tile_and_polygon_by_ref(poly_ref).is_some()
+    }
+
+    /// Returns the tile & polygon ``poly_ref`` refers to or ``None`` if the handle is stale.
+    pub fn get_tile_and_polygon_by_ref(
+        &self,
+        poly_ref: PolyRef,
+    ) -> Option<(&NavMeshTile, &Polygon)> {
+        let tile_coord = poly_ref.tile();
+        if self.get_tile_salt(tile_coord) != poly_ref.salt() {
+            return None;
+        }
+
+        let tile = self.tiles.get(&tile_coord)?;
+        let polygon = tile.polygons.get(poly_ref.polygon() as usize)?;
+
+        Some((tile, polygon))
+    }
+
     pub(super) fn add_tile(
         &mut self,
         tile_coord: UVec2,
@@ -108,6 +540,7 @@ impl NavMeshTiles {
                     opposite_direction,
                     false,
                     step_height,
+                    nav_mesh_settings.deterministic,
                 );
                 connect_external_links(
                     neighbour,
@@ -116,6 +549,7 @@ impl NavMeshTiles {
                     direction,
                     previous_tile_existed,
                     step_height,
+                    nav_mesh_settings.deterministic,
                 );
             }
         }
@@ -135,6 +569,7 @@ impl NavMeshTiles {
                     opposite_direction,
                     false,
                     step_height,
+                    nav_mesh_settings.deterministic,
                 );
                 connect_external_links(
                     neighbour,
@@ -143,6 +578,7 @@ impl NavMeshTiles {
                     direction,
                     previous_tile_existed,
                     step_height,
+                    nav_mesh_settings.deterministic,
                 );
             }
         }
@@ -162,6 +598,7 @@ impl NavMeshTiles {
                     opposite_direction,
                     false,
                     step_height,
+                    nav_mesh_settings.deterministic,
                 );
                 connect_external_links(
                     neighbour,
@@ -170,6 +607,7 @@ impl NavMeshTiles {
                     direction,
                     previous_tile_existed,
                     step_height,
+                    nav_mesh_settings.deterministic,
                 );
             }
         }
@@ -189,6 +627,7 @@ impl NavMeshTiles {
                     opposite_direction,
                     false,
                     step_height,
+                    nav_mesh_settings.deterministic,
                 );
                 connect_external_links(
                     neighbour,
@@ -197,6 +636,7 @@ impl NavMeshTiles {
                     direction,
                     previous_tile_existed,
                     step_height,
+                    nav_mesh_settings.deterministic,
                 );
             }
         }
@@ -245,18 +685,58 @@ impl NavMeshTiles {
         self.tiles.remove(&tile_coord);
     }
 
-    /// Returns the closest polygon in a box around ``center`` as a tuple of (tile coordinate, polygon index, position on triangle).
+    /// Sets ``flags_to_set`` & clears ``flags_to_clear`` on every polygon whose bounds overlap the box between ``min`` & ``max``.
+    ///
+    /// Returns how many polygons were touched. Changes are lost when a tile is regenerated. Parts of the box outside [NavMeshSettings::world_bounds] are ignored.
+    pub fn set_polygon_flags_in_box(
+        &mut self,
+        nav_mesh_settings: &NavMeshSettings,
+        min: Vec3,
+        max: Vec3,
+        flags_to_set: u16,
+        flags_to_clear: u16,
+    ) -> usize {
+        let Some((min_tile, max_tile)) =
+            nav_mesh_settings.get_tiles_overlapping(min.xz(), max.xz())
+        else {
+            return 0;
+        };
+
+        let mut count = 0;
+        for x in min_tile.x..=max_tile.x {
+            for y in min_tile.y..=max_tile.y {
+                let Some(tile) = self.tiles.get_mut(&UVec2::new(x, y)) else {
+                    continue;
+                };
+
+                let polygons = &mut tile.polygons;
+                tile.bvh.query_aabb(min, max, |poly_i| {
+                    let polygon = &mut polygons[poly_i as usize];
+                    polygon.flags = (polygon.flags | flags_to_set) & !flags_to_clear;
+
+                    count += 1;
+                });
+            }
+        }
+
+        count
+    }
+
+    /// Returns the closest polygon in a box around ``center`` as a tuple of ([PolyRef], position on triangle).
+    ///
+    /// Only polygons whose bounds overlap the box are considered, parts of the box outside [NavMeshSettings::world_bounds] are ignored. Polygons not passing ``filter`` are ignored. If **``None``** is supplied [PolygonFilter::default] is used.
     pub fn find_closest_polygon_in_box(
         &self,
         nav_mesh_settings: &NavMeshSettings,
         center: Vec3,
         half_extents: f32,
-    ) -> Option<(UVec2, u16, Vec3)> {
+        filter: Option<PolygonFilter>,
+    ) -> Option<(PolyRef, Vec3)> {
+        let filter = filter.unwrap_or_default();
         let min = center - half_extents;
         let max = center + half_extents;
 
-        let min_tile = nav_mesh_settings.get_tile_containing_position(min.xz());
-        let max_tile = nav_mesh_settings.get_tile_containing_position(max.xz());
+        let (min_tile, max_tile) = nav_mesh_settings.get_tiles_overlapping(min.xz(), max.xz())?;
 
         let mut out_polygon = None;
         let mut out_distance = f32::INFINITY;
@@ -264,21 +744,169 @@ impl NavMeshTiles {
             for y in min_tile.y..=max_tile.y {
                 let tile_coords = UVec2::new(x, y);
                 if let Some(tile) = self.tiles.get(&tile_coords) {
-                    for (poly_i, polygon) in tile.polygons.iter().enumerate() {
+                    let salt = self.get_tile_salt(tile_coords);
+
+                    tile.bvh.query_aabb(min, max, |poly_i| {
+                        let polygon = &tile.polygons[poly_i as usize];
+                        if !filter.passes(polygon.flags) {
+                            return;
+                        }
+
                         let closest_point = tile.get_closest_point_in_polygon(polygon, center);
                         let closest_distance = closest_point.distance_squared(center);
 
                         if closest_distance < out_distance {
                             out_distance = closest_distance;
-                            out_polygon = Some((tile_coords, poly_i as u16, closest_point));
+                            out_polygon =
+                                Some((PolyRef::new(tile_coords, salt, poly_i), closest_point));
                         }
-                    }
+                    });
                 }
             }
         }
 
         out_polygon
     }
+
+    /// Returns the polygon directly below or above ``position`` within ``height_range`` as a tuple of ([PolyRef], position on triangle).
+    ///
+    /// If several polygons overlap ``position`` on the XZ-plane the one closest vertically is returned. Polygons not passing ``filter`` are ignored.
+    /// Returns ``None`` if ``position`` is outside [NavMeshSettings::world_bounds].
+    pub fn find_polygon_at_point(
+        &self,
+        nav_mesh_settings: &NavMeshSettings,
+        position: Vec3,
+        height_range: f32,
+        filter: Option<PolygonFilter>,
+    ) -> Option<(PolyRef, Vec3)> {
+        let filter = filter.unwrap_or_default();
+        if !nav_mesh_settings.is_within_world_bounds(position.xz()) {
+            return None;
+        }
+        let tile_coords = nav_mesh_settings.get_tile_containing_position(position.xz());
+        let tile = self.tiles.get(&tile_coords)?;
+
+        tile.find_polygon_at_point(position, height_range, &filter)
+            .map(|(polygon, position)| {
+                (
+                    PolyRef::new(tile_coords, self.get_tile_salt(tile_coords), polygon),
+                    position,
+                )
+            })
+    }
+
+    /// Casts a ray against the nav-mesh polygons, returning the first hit as a tuple of ([PolyRef], hit position).
+    ///
+    /// * ``origin`` - Start of the ray.
+    /// * ``direction`` - Direction of the ray. Does not need to be normalized.
+    /// * ``max_distance`` - Maximum distance along the ray to check, in world units.
+    /// * ``filter`` - Polygons not passing the filter are ignored. If **``None``** is supplied [PolygonFilter::default] is used.
+    pub fn raycast_polygons(
+        &self,
+        nav_mesh_settings: &NavMeshSettings,
+        origin: Vec3,
+        direction: Vec3,
+        max_distance: f32,
+        filter: Option<PolygonFilter>,
+    ) -> Option<(PolyRef, Vec3)> {
+        let filter = filter.unwrap_or_default();
+        let direction = direction.try_normalize()?;
+        let end = origin + direction * max_distance;
+
+        let (min_tile, max_tile) = nav_mesh_settings
+            .get_tiles_overlapping(origin.xz().min(end.xz()), origin.xz().max(end.xz()))?;
+
+        let mut out_polygon = None;
+        let mut out_distance = max_distance;
+        for x in min_tile.x..=max_tile.x {
+            for y in min_tile.y..=max_tile.y {
+                let tile_coords = UVec2::new(x, y);
+                let Some(tile) = self.tiles.get(&tile_coords) else {
+                    continue;
+                };
+                let salt = self.get_tile_salt(tile_coords);
+
+                tile.bvh
+                    .query_ray(origin, direction, out_distance, |poly_i| {
+                        let polygon = &tile.polygons[poly_i as usize];
+                        if !filter.passes(polygon.flags) {
+                            return;
+                        }
+
+                        if let Some(distance) =
+                            tile.raycast_polygon(polygon, origin, direction, out_distance)
+                        {
+                            out_distance = distance;
+                            out_polygon = Some((
+                                PolyRef::new(tile_coords, salt, poly_i),
+                                origin + direction * distance,
+                            ));
+                        }
+                    });
+            }
+        }
+
+        out_polygon
+    }
+
+    /// Calls ``callback`` with the start & end of every wall edge belonging to a polygon overlapping the box between ``min`` & ``max``.
+    ///
+    /// A wall is a polygon edge that doesn't link to another polygon, or only links to polygons within the same tile that don't pass ``filter``. Polygons not passing ``filter`` are ignored.
+    pub fn find_walls_in_box(
+        &self,
+        nav_mesh_settings: &NavMeshSettings,
+        min: Vec3,
+        max: Vec3,
+        filter: Option<PolygonFilter>,
+        mut callback: impl FnMut(Vec3, Vec3),
+    ) {
+        let filter = filter.unwrap_or_default();
+
+        let Some((min_tile, max_tile)) =
+            nav_mesh_settings.get_tiles_overlapping(min.xz(), max.xz())
+        else {
+            return;
+        };
+
+        for x in min_tile.x..=max_tile.x {
+            for y in min_tile.y..=max_tile.y {
+                let Some(tile) = self.tiles.get(&UVec2::new(x, y)) else {
+                    continue;
+                };
+
+                tile.bvh.query_aabb(min, max, |poly_i| {
+                    let polygon = &tile.polygons[poly_i as usize];
+                    if !filter.passes(polygon.flags) {
+                        return;
+                    }
+
+                    for i in 0..VERTICES_IN_TRIANGLE {
+                        let is_open = polygon.links.iter().any(|link| match link {
+                            Link::Internal {
+                                edge,
+                                neighbour_polygon,
+                            } => {
+                                *edge as usize == i
+                                    && filter
+                                        .passes(tile.polygons[*neighbour_polygon as usize].flags)
+                            }
+                            Link::External { edge, .. } => *edge as usize == i,
+                            Link::OffMesh { .. } => false,
+                        });
+                        if is_open {
+                            continue;
+                        }
+
+                        let a = tile.vertices[polygon.indices[i] as usize];
+                        let b =
+                            tile.vertices[polygon.indices[(i + 1) % VERTICES_IN_TRIANGLE] as usize];
+
+                        callback(a, b);
+                    }
+                });
+            }
+        }
+    }
 }
 
 fn get_height_in_triangle(vertices: &[Vec3; VERTICES_IN_TRIANGLE], position: Vec3) -> Option<f32> {
@@ -388,7 +1016,7 @@ fn in_polygon(vertices: &[Vec3; VERTICES_IN_TRIANGLE], position: Vec3) -> bool {
 fn remove_links_to_direction(tile: &mut NavMeshTile, remove_direction: EdgeConnectionDirection) {
     for polygon in tile.polygons.iter_mut() {
         polygon.links.retain(|link| match link {
-            Link::Internal { .. } => true,
+            Link::Internal { .. } | Link::OffMesh { .. } => true,
             Link::External { direction, .. } => *direction != remove_direction,
         });
     }
@@ -401,11 +1029,12 @@ fn connect_external_links(
     neighbour_to_self_direction: EdgeConnectionDirection,
     remove_existing_links: bool,
     step_height: f32,
+    sort_links: bool,
 ) {
     for (poly_index, polygon) in tile.polygons.iter_mut().enumerate() {
         if remove_existing_links {
             polygon.links.retain(|link| match link {
-                Link::Internal { .. } => true,
+                Link::Internal { .. } | Link::OffMesh { .. } => true,
                 Link::External { direction, .. } => *direction != neighbour_direction,
             });
         }
@@ -467,6 +1096,11 @@ fn connect_external_links(
             }));
             break; // We can only have one edge parallel to the direction in a triangle.
         }
+
+        if sort_links {
+            // Links are otherwise ordered by when each neighbouring tile was added.
+            polygon.links.sort_unstable();
+        }
     }
 }
 
@@ -610,7 +1244,7 @@ pub(super) fn create_nav_mesh_tile_from_poly_mesh(
     nav_mesh_settings: &NavMeshSettings,
 ) -> NavMeshTile {
     // Slight worry that the compiler won't optimize this but damn, it's cool.
-    let polygons = poly_mesh
+    let polygons: Box<[Polygon]> = poly_mesh
         .polygons
         .into_iter()
         .zip(poly_mesh.edges.iter())
@@ -636,6 +1270,7 @@ pub(super) fn create_nav_mesh_tile_from_poly_mesh(
                 links,
                 indices,
                 area,
+                flags: DEFAULT_POLYGON_FLAGS,
             }
         })
         .collect();
@@ -652,11 +1287,327 @@ pub(super) fn create_nav_mesh_tile_from_poly_mesh(
                 tile_origin.y + vertex.z as f32 * nav_mesh_settings.cell_width,
             )
         })
-        .collect();
+        .collect::<Box<[Vec3]>>();
+
+    let bvh = PolygonBvh::build(&vertices, &polygons);
 
     NavMeshTile {
         vertices,
         edges: poly_mesh.edges.into_boxed_slice(),
         polygons,
+        bvh,
+        off_mesh_links: Box::new([]),
+        input_hash: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_settings() -> NavMeshSettings {
+        NavMeshSettings::from_agent_and_bounds(0.5, 2.0, 20.0, -10.0).with_tile_width(40)
+    }
+
+    /// Returns the bounds of ``polygon`` in ``tile``.
+    fn get_polygon_bounds(tile: &NavMeshTile, polygon: &Polygon) -> (Vec3, Vec3) {
+        polygon
+            .indices
+            .iter()
+            .fold((Vec3::INFINITY, Vec3::NEG_INFINITY), |(min, max), index| {
+                let vertex = tile.vertices[*index as usize];
+                (min.min(vertex), max.max(vertex))
+            })
+    }
+
+    /// Tile with a bumpy floor & a sloped upper floor over part of it.
+    fn build_test_tile(nav_mesh_settings: &NavMeshSettings, tile_coord: UVec2) -> NavMeshTile {
+        const CELLS: u32 = 5;
+
+        let (tile_min, tile_max) = nav_mesh_settings.get_tile_bounds(tile_coord);
+        let cell_size = (tile_max - tile_min) / CELLS as f32;
+
+        let mut vertices = Vec::new();
+        let mut polygons = Vec::new();
+        let mut add_layer = |columns: u32, height: &dyn Fn(Vec2) -> f32| {
+            let first_vertex = vertices.len() as u32;
+            for z in 0..=CELLS {
+                for x in 0..=columns {
+                    let position = tile_min + Vec2::new(x as f32, z as f32) * cell_size;
+                    vertices.push(Vec3::new(position.x, height(position), position.y));
+                }
+            }
+
+            let index = |x: u32, z: u32| first_vertex + z * (columns + 1) + x;
+            for z in 0..CELLS {
+                for x in 0..columns {
+                    for indices in [
+                        [index(x, z), index(x + 1, z), index(x + 1, z + 1)],
+                        [index(x, z), index(x + 1, z + 1), index(x, z + 1)],
+                    ] {
+                        polygons.push(Polygon {
+                            indices,
+                            links: SmallVec::new(),
+                            area: Area(0),
+                            flags: DEFAULT_POLYGON_FLAGS,
+                        });
+                    }
+                }
+            }
+        };
+        add_layer(CELLS, &|position| {
+            position.x.sin() * 0.3 + position.y.cos() * 0.2
+        });
+        add_layer(2, &|position| 2.0 + position.x * 0.05);
+
+        let bvh = PolygonBvh::build(&vertices, &polygons);
+
+        NavMeshTile {
+            edges: vec![[EdgeConnection::None; VERTICES_IN_TRIANGLE]; polygons.len()]
+                .into_boxed_slice(),
+            vertices: vertices.into_boxed_slice(),
+            polygons: polygons.into_boxed_slice(),
+            bvh,
+            off_mesh_links: Box::default(),
+            input_hash: None,
+        }
+    }
+
+    fn build_test_nav_mesh(nav_mesh_settings: &NavMeshSettings) -> NavMeshTiles {
+        let mut nav_mesh = NavMeshTiles::default();
+        let max_tile = nav_mesh_settings.get_max_tile();
+        for x in 0..=max_tile.x {
+            for y in 0..=max_tile.y {
+                let tile_coord = UVec2::new(x, y);
+                nav_mesh
+                    .tiles
+                    .insert(tile_coord, build_test_tile(nav_mesh_settings, tile_coord));
+            }
+        }
+
+        nav_mesh
+    }
+
+    #[test]
+    fn test_polygon_queries_match_brute_force() {
+        let nav_mesh_settings = get_test_settings();
+        let nav_mesh = build_test_nav_mesh(&nav_mesh_settings);
+
+        let mut positions = Vec::new();
+        for x in -12..=12 {
+            for z in -12..=12 {
+                for y in [0.1, 1.0, 2.2] {
+                    positions.push(Vec3::new(x as f32 * 1.3 + 0.05, y, z as f32 * 1.3 + 0.05));
+                }
+            }
+        }
+
+        for position in positions.iter().copied() {
+            // Point query.
+            let height_range = 1.0;
+            let tile_coord = nav_mesh_settings.get_tile_containing_position(position.xz());
+            let expected = nav_mesh.get_tiles().get(&tile_coord).and_then(|tile| {
+                tile.polygons
+                    .iter()
+                    .map(|polygon| tile.get_closest_point_in_polygon(polygon, position))
+                    .filter(|point| {
+                        point.xz() == position.xz() && (point.y - position.y).abs() <= height_range
+                    })
+                    .min_by(|a, b| {
+                        (a.y - position.y)
+                            .abs()
+                            .total_cmp(&(b.y - position.y).abs())
+                    })
+            });
+            let result = nav_mesh
+                .find_polygon_at_point(&nav_mesh_settings, position, height_range, None)
+                .map(|(_, point)| point);
+            assert_eq!(result, expected, "Point query differs at {position}");
+
+            // Box query, only polygons overlapping the box are considered.
+            let half_extents = 2.0;
+            let (min, max) = (position - half_extents, position + half_extents);
+            let min_tile = nav_mesh_settings.get_tile_containing_position(min.xz());
+            let max_tile = nav_mesh_settings.get_tile_containing_position(max.xz());
+            let expected = nav_mesh
+                .get_tiles()
+                .iter()
+                .filter(|(tile_coord, _)| {
+                    tile_coord.cmpge(min_tile).all() && tile_coord.cmple(max_tile).all()
+                })
+                .flat_map(|(_, tile)| {
+                    tile.polygons
+                        .iter()
+                        .filter(|polygon| {
+                            let (polygon_min, polygon_max) = get_polygon_bounds(tile, polygon);
+                            polygon_min.cmple(max).all() && polygon_max.cmpge(min).all()
+                        })
+                        .map(|polygon| {
+                            tile.get_closest_point_in_polygon(polygon, position)
+                                .distance(position)
+                        })
+                })
+                .min_by(f32::total_cmp);
+            let result = nav_mesh
+                .find_closest_polygon_in_box(&nav_mesh_settings, position, half_extents, None)
+                .map(|(_, point)| point.distance(position));
+            assert_eq!(result, expected, "Box query differs at {position}");
+
+            // Ray queries, straight down & at an angle.
+            for direction in [Vec3::NEG_Y, Vec3::new(1.0, -1.0, 0.5).normalize()] {
+                let origin = position + Vec3::Y * 3.0;
+                let max_distance = 10.0;
+                let expected = nav_mesh
+                    .get_tiles()
+                    .values()
+                    .flat_map(|tile| {
+                        tile.polygons.iter().filter_map(|polygon| {
+                            tile.raycast_polygon(polygon, origin, direction, max_distance)
+                        })
+                    })
+                    .min_by(f32::total_cmp);
+                let result = nav_mesh
+                    .raycast_polygons(&nav_mesh_settings, origin, direction, max_distance, None)
+                    .map(|(_, point)| point.distance(origin));
+                match (result, expected) {
+                    (Some(result), Some(expected)) => assert!(
+                        (result - expected).abs() < 1e-4,
+                        "Ray query differs at {origin}: {result} != {expected}"
+                    ),
+                    (result, expected) => {
+                        assert_eq!(result, expected, "Ray query differs at {origin}")
+                    }
+                }
+            }
+
+            // Rays pointing away from the nav-mesh never hit.
+            assert!(nav_mesh
+                .raycast_polygons(
+                    &nav_mesh_settings,
+                    position + Vec3::Y * 3.0,
+                    Vec3::Y,
+                    10.0,
+                    None
+                )
+                .is_none());
+        }
+
+        // The bounding volume hierarchy returns exactly the polygons whose bounds overlap the box.
+        for (tile_coord, tile) in nav_mesh.get_tiles() {
+            let (tile_min, tile_max) = nav_mesh_settings.get_tile_bounds(*tile_coord);
+            let min = Vec3::new(tile_min.x + 2.5, -1.0, tile_min.y + 2.5);
+            let max = Vec3::new(tile_max.x - 4.0, 1.0, tile_max.y - 4.0);
+
+            let mut result = Vec::new();
+            tile.bvh
+                .query_aabb(min, max, |polygon| result.push(polygon));
+            result.sort_unstable();
+            let expected: Vec<u16> = (0..tile.polygons.len() as u16)
+                .filter(|polygon| {
+                    let (polygon_min, polygon_max) =
+                        get_polygon_bounds(tile, &tile.polygons[*polygon as usize]);
+                    polygon_min.cmple(max).all() && polygon_max.cmpge(min).all()
+                })
+                .collect();
+            assert!(!expected.is_empty());
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn test_empty_tile_queries() {
+        let nav_mesh_settings = get_test_settings();
+
+        // Queries on a tile without polygons find nothing.
+        let mut empty_nav_mesh = NavMeshTiles::default();
+        empty_nav_mesh.tiles.insert(
+            UVec2::new(2, 2),
+            NavMeshTile {
+                vertices: Box::default(),
+                polygons: Box::default(),
+                edges: Box::default(),
+                bvh: PolygonBvh::default(),
+                off_mesh_links: Box::default(),
+                input_hash: None,
+            },
+        );
+        let position = nav_mesh_settings
+            .get_tile_bounds(UVec2::new(2, 2))
+            .0
+            .extend(0.0)
+            .xzy()
+            + Vec3::new(1.0, 0.0, 1.0);
+        assert!(empty_nav_mesh
+            .find_polygon_at_point(&nav_mesh_settings, position, 1.0, None)
+            .is_none());
+        assert!(empty_nav_mesh
+            .find_closest_polygon_in_box(&nav_mesh_settings, position, 5.0, None)
+            .is_none());
+        assert!(empty_nav_mesh
+            .raycast_polygons(
+                &nav_mesh_settings,
+                position + Vec3::Y,
+                Vec3::NEG_Y,
+                5.0,
+                None
+            )
+            .is_none());
+        PolygonBvh::default().query_aabb(Vec3::splat(-1000.0), Vec3::splat(1000.0), |_| {
+            panic!("Empty bounding volume hierarchy returned a polygon.")
+        });
+        PolygonBvh::default().query_ray(Vec3::ZERO, Vec3::NEG_Y, 1000.0, |_| {
+            panic!("Empty bounding volume hierarchy returned a polygon.")
+        });
+    }
+
+    #[test]
+    fn test_poly_ref_packing() {
+        for (tile, salt, polygon) in [
+            (UVec2::ZERO, 0, 0),
+            (UVec2::new(3, 70), 5, 12),
+            (UVec2::splat(u16::MAX.into()), u16::MAX, u16::MAX),
+        ] {
+            let poly_ref = PolyRef::new(tile, salt, polygon);
+            assert_eq!(
+                (poly_ref.tile(), poly_ref.salt(), poly_ref.polygon()),
+                (tile, salt, polygon)
+            );
+            // Handles survive a round trip through their packed form.
+            assert_eq!(PolyRef::from_bits(poly_ref.to_bits()), poly_ref);
+        }
+
+        assert_ne!(
+            PolyRef::new(UVec2::new(1, 0), 0, 0),
+            PolyRef::new(UVec2::new(0, 1), 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_poly_ref_validity() {
+        let nav_mesh_settings = get_test_settings();
+        let tile_coord = UVec2::new(1, 1);
+
+        let mut nav_mesh = NavMeshTiles::default();
+        nav_mesh
+            .tiles
+            .insert(tile_coord, build_test_tile(&nav_mesh_settings, tile_coord));
+        nav_mesh.tile_generations.insert(tile_coord, 7);
+
+        let poly_ref = nav_mesh
+            .get_poly_ref(tile_coord, 3)
+            .expect("Polygon is missing.");
+        assert_eq!(poly_ref.salt(), 7);
+        assert!(nav_mesh.is_valid_poly_ref(poly_ref));
+        let polygon_count = nav_mesh.tiles[&tile_coord].polygons.len() as u16;
+        assert!(nav_mesh.get_poly_ref(tile_coord, polygon_count).is_none());
+        assert!(nav_mesh.get_poly_ref(UVec2::new(2, 1), 0).is_none());
+
+        // Regenerating the tile makes existing handles stale.
+        nav_mesh.tile_generations.insert(tile_coord, 8);
+        assert!(!nav_mesh.is_valid_poly_ref(poly_ref));
+
+        nav_mesh.tiles.remove(&tile_coord);
+        nav_mesh.tile_generations.insert(tile_coord, 7);
+        assert!(!nav_mesh.is_valid_poly_ref(poly_ref));
     }
 }
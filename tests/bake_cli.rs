@@ -0,0 +1,99 @@
+use std::{fs, process::Command};
+
+use oxidized_navigation::{bake::BakedNavMesh, NavMeshSettings};
+
+#[test]
+fn test_bake_cli_obj() {
+    let directory = std::env::temp_dir().join(format!("bake_cli_{}", std::process::id()));
+    fs::create_dir_all(&directory).expect("Failed to create test directory.");
+
+    // Two 30x30 quads, large enough to cover several tiles.
+    let obj = "\
+v -15 0 -15
+v 15 0 -15
+v 15 0 15
+v -15 0 15
+f 1 3 2
+f 1 4 3
+o second
+v 20 1 -15
+v 50 1 -15
+v 50 1 15
+v 20 1 15
+f 5 7 6
+f 5 8 7
+";
+    let obj_path = directory.join("level.obj");
+    fs::write(&obj_path, obj).expect("Failed to write OBJ.");
+
+    let settings = NavMeshSettings::from_agent_and_bounds(0.5, 1.9, 100.0, -10.0);
+    let settings_path = directory.join("settings.toml");
+    fs::write(
+        &settings_path,
+        toml::to_string(&settings).expect("Failed to serialize settings."),
+    )
+    .expect("Failed to write settings.");
+
+    let output_path = directory.join("level.navmesh");
+    let output = Command::new(env!("CARGO_BIN_EXE_bake_nav_mesh"))
+        .arg(&settings_path)
+        .arg(&output_path)
+        .arg(&obj_path)
+        .output()
+        .expect("Failed to run bake_nav_mesh.");
+    assert!(
+        output.status.success(),
+        "bake_nav_mesh failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let baked_nav_mesh: BakedNavMesh =
+        bincode::deserialize(&fs::read(&output_path).expect("Failed to read baked nav-mesh."))
+            .expect("Failed to deserialize baked nav-mesh.");
+    assert_eq!(baked_nav_mesh.settings.cell_width, settings.cell_width);
+    assert_eq!(baked_nav_mesh.settings.tile_width, settings.tile_width);
+    assert!(baked_nav_mesh.tiles.len() > 1);
+    let tile_coords: Vec<_> = baked_nav_mesh
+        .tiles
+        .iter()
+        .map(|baked_tile| (baked_tile.tile_coord.y, baked_tile.tile_coord.x))
+        .collect();
+    let mut sorted_tile_coords = tile_coords.clone();
+    sorted_tile_coords.sort();
+    sorted_tile_coords.dedup();
+    assert_eq!(tile_coords, sorted_tile_coords);
+
+    // The quads are walkable & their polygons face up.
+    let polygons: usize = baked_nav_mesh
+        .tiles
+        .iter()
+        .map(|baked_tile| baked_tile.tile.polygons.len())
+        .sum();
+    assert!(polygons > 0);
+    for baked_tile in baked_nav_mesh.tiles.iter() {
+        let tile = &baked_tile.tile;
+        for polygon in tile.polygons.iter() {
+            let [a, b, c] = polygon.indices.map(|index| tile.vertices[index as usize]);
+            assert!((b - a).cross(c - a).y >= 0.0, "Polygon faces down.");
+            assert!(a.y > -0.5 && a.y < 1.5, "Vertex off the quads: {a}");
+        }
+    }
+
+    // Re-encoding gives the same bytes.
+    assert_eq!(
+        bincode::serialize(&baked_nav_mesh).expect("Failed to serialize baked nav-mesh."),
+        fs::read(&output_path).expect("Failed to read baked nav-mesh.")
+    );
+
+    let report =
+        fs::read_to_string(output_path.with_extension("report.toml")).expect("No report written.");
+    let report: toml::Table = toml::from_str(&report).expect("Failed to parse report.");
+    assert_eq!(report["input_files"].as_integer(), Some(1));
+    assert_eq!(report["input_triangles"].as_integer(), Some(4));
+    assert_eq!(
+        report["tiles"].as_integer(),
+        Some(baked_nav_mesh.tiles.len() as i64)
+    );
+
+    fs::remove_dir_all(&directory).ok();
+}
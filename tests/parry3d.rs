@@ -1,15 +1,33 @@
 use std::{num::NonZeroU16, time::Duration};
 
-use bevy::prelude::*;
+use bevy::{prelude::*, time::TimeUpdateStrategy};
 use oxidized_navigation::{
-    colliders::OxidizedCollider, query::find_path, ActiveGenerationTasks, NavMesh, NavMeshAffector,
-    NavMeshSettings, OxidizedNavigationPlugin,
+    agent::{
+        NavAgent, NavAgentArrived, NavAgentPathFailed, NavAgentStatus,
+        OxidizedNavigationAgentPlugin,
+    },
+    bake::{bake_nav_mesh, load_baked_nav_mesh, BakedNavMesh},
+    colliders::OxidizedCollider,
+    conversion::Tessellation,
+    crowd::{CrowdAgent, CrowdSettings, OxidizedNavigationCrowdPlugin},
+    export::{write_glb, write_obj},
+    query::{find_path, find_polygon_path_between_refs, FindPathError, FindPolygonPathError},
+    tiles::{NavMeshTiles, OffMeshLinkKind, PolygonFilter},
+    ActiveGenerationTasks, Area, JumpLinkSettings, NavMesh, NavMeshAffector, NavMeshAreaVolume,
+    NavMeshBlocker, NavMeshDoor, NavMeshObstacle, NavMeshPriorityAnchor, NavMeshSettings,
+    NavMeshStaticAffector, NavMeshStreamingAnchor, NavMeshTessellation, NavMeshTilePriority,
+    NavMeshVolumeShape, OutOfWorldBounds, OxidizedNavigationPlugin, RegionPartitioning,
+    SpanFilters, StreamingSettings, TileGenerated,
 };
 use parry3d_016::shape::SharedShape;
 
 const TIMEOUT_DURATION: Duration = Duration::new(15, 0);
 const SLEEP_DURATION: Duration = Duration::from_millis(2);
 
+/// Positions on either side of the thin wall in [setup_world_system].
+const START_POS: Vec3 = Vec3::new(5.0, 1.0, 5.0);
+const END_POS: Vec3 = Vec3::new(-15.0, 1.0, -15.0);
+
 #[derive(Component)]
 struct MyParryCollider {
     collider: SharedShape,
@@ -25,71 +43,132 @@ impl OxidizedCollider for MyParryCollider {
     }
 }
 
-fn setup_world_system(mut commands: Commands) {
-    // Plane
-    commands.spawn((
-        TransformBundle::IDENTITY,
-        MyParryCollider {
-            collider: SharedShape::cuboid(25.0, 0.1, 25.0),
-        },
+fn affector_bundle(transform: Transform, collider: SharedShape) -> impl Bundle {
+    (
+        TransformBundle::from_transform(transform),
+        MyParryCollider { collider },
         NavMeshAffector,
-    ));
+    )
+}
+
+/// 50 by 50 floor centered on ``translation``.
+fn floor_bundle(translation: Vec3) -> impl Bundle {
+    affector_bundle(
+        Transform::from_translation(translation),
+        SharedShape::cuboid(25.0, 0.1, 25.0),
+    )
+}
 
-    // Cube
-    commands.spawn((
-        TransformBundle::from_transform(Transform::from_xyz(-5.0, 0.8, -5.0)),
-        MyParryCollider {
-            collider: SharedShape::cuboid(1.25, 1.25, 1.25),
+fn cube_bundle(translation: Vec3, half_extent: f32) -> impl Bundle {
+    affector_bundle(
+        Transform::from_translation(translation),
+        SharedShape::cuboid(half_extent, half_extent, half_extent),
+    )
+}
+
+/// Obstacle standing on the ground below ``position``.
+fn obstacle_bundle(position: Vec3) -> impl Bundle {
+    (
+        TransformBundle::from_transform(Transform::from_translation(position.with_y(0.0))),
+        NavMeshObstacle::Cylinder {
+            radius: 3.0,
+            height: 2.0,
         },
-        NavMeshAffector,
-    ));
+    )
+}
+
+fn setup_world_system(mut commands: Commands) {
+    commands.spawn(floor_bundle(Vec3::ZERO));
+    commands.spawn(cube_bundle(Vec3::new(-5.0, 0.8, -5.0), 1.25));
 
     // Tall Cube
-    commands.spawn((
-        TransformBundle::from_transform(
-            Transform::from_xyz(-0.179, 18.419, -27.744).with_scale(Vec3::new(15.0, 15.0, 15.0)),
-        ),
-        MyParryCollider {
-            collider: SharedShape::cuboid(1.25, 1.25, 1.25),
-        },
-        NavMeshAffector,
+    commands.spawn(affector_bundle(
+        Transform::from_xyz(-0.179, 18.419, -27.744).with_scale(Vec3::new(15.0, 15.0, 15.0)),
+        SharedShape::cuboid(1.25, 1.25, 1.25),
     ));
 
     // Thin wall
-    commands.spawn((
-        TransformBundle::from_transform(
-            Transform::from_xyz(-3.0, 0.8, 5.0).with_scale(Vec3::new(50.0, 15.0, 1.0)),
-        ),
-        MyParryCollider {
-            collider: SharedShape::cuboid(0.05, 0.05, 0.05),
-        },
-        NavMeshAffector,
+    commands.spawn(affector_bundle(
+        Transform::from_xyz(-3.0, 0.8, 5.0).with_scale(Vec3::new(50.0, 15.0, 1.0)),
+        SharedShape::cuboid(0.05, 0.05, 0.05),
     ));
 }
 
+fn get_test_settings() -> NavMeshSettings {
+    NavMeshSettings {
+        cell_width: 0.25,
+        cell_height: 0.1,
+        tile_width: 100,
+        world_bounds: Rect::from_center_half_size(Vec2::ZERO, Vec2::splat(250.0)),
+        world_bottom_bound: -100.0,
+        max_traversable_slope_radians: (40.0_f32 - 0.1).to_radians(),
+        walkable_height: 20,
+        walkable_radius: 1,
+        step_height: 3,
+        min_region_area: 100,
+        max_region_area_to_merge_into: 500,
+        max_contour_simplification_error: 1.1,
+        max_edge_length: 80,
+        max_tile_generation_tasks: NonZeroU16::new(8), // Github Actions are limited to 7 GB.
+        max_rasterization_tasks: None,
+        cache_open_tiles: false,
+        jump_links: None,
+        partitioning: RegionPartitioning::Watershed,
+        span_filters: SpanFilters::default(),
+        median_area_filter: false,
+        tessellation: Tessellation::default(),
+        streaming: None,
+        affector_debounce: None,
+        deterministic: false,
+    }
+}
+
 fn setup_app(app: &mut App) {
     app.add_plugins((
         MinimalPlugins,
         TransformPlugin,
-        OxidizedNavigationPlugin::<MyParryCollider>::new(NavMeshSettings {
-            cell_width: 0.25,
-            cell_height: 0.1,
-            tile_width: 100,
-            world_half_extents: 250.0,
-            world_bottom_bound: -100.0,
-            max_traversable_slope_radians: (40.0_f32 - 0.1).to_radians(),
-            walkable_height: 20,
-            walkable_radius: 1,
-            step_height: 3,
-            min_region_area: 100,
-            max_region_area_to_merge_into: 500,
-            max_contour_simplification_error: 1.1,
-            max_edge_length: 80,
-            max_tile_generation_tasks: NonZeroU16::new(8), // Github Actions are limited to 7 GB.
-        }),
+        OxidizedNavigationPlugin::<MyParryCollider>::new(get_test_settings()),
     ));
 }
 
+/// Same as [setup_app], with the schedule [bake_nav_mesh] requires.
+fn setup_bake_app(app: &mut App) {
+    app.add_plugins((
+        MinimalPlugins,
+        TransformPlugin,
+        OxidizedNavigationPlugin::<MyParryCollider>::new(get_test_settings()).with_bake_schedule(),
+    ));
+}
+
+/// Creates an app with [setup_app], changing the settings with ``update_settings`` & spawning the world with ``setup_world``.
+fn create_app<M>(
+    update_settings: impl FnOnce(&mut NavMeshSettings),
+    setup_world: impl IntoSystemConfigs<M>,
+) -> App {
+    let mut app = App::new();
+
+    setup_app(&mut app);
+    update_settings(&mut app.world_mut().resource_mut::<NavMeshSettings>());
+
+    app.add_systems(Startup, setup_world);
+
+    app
+}
+
+/// Creates an app with [setup_bake_app] recording [GeneratedTiles], with the world from [setup_world_system] spawned & its transforms propagated.
+fn create_bake_app(update_settings: impl FnOnce(&mut NavMeshSettings)) -> App {
+    let mut app = App::new();
+
+    setup_bake_app(&mut app);
+    update_settings(&mut app.world_mut().resource_mut::<NavMeshSettings>());
+    record_generated_tiles(&mut app);
+
+    app.add_systems(Startup, setup_world_system);
+    app.update();
+
+    app
+}
+
 fn wait_for_generation_to_finish(app: &mut App) {
     loop {
         app.update();
@@ -104,27 +183,1412 @@ fn wait_for_generation_to_finish(app: &mut App) {
     }
 }
 
+/// Calls ``f`` with the app's nav-mesh & settings.
+fn with_nav_mesh<T>(app: &App, f: impl FnOnce(&NavMeshTiles, &NavMeshSettings) -> T) -> T {
+    let nav_mesh_settings = app.world().resource::<NavMeshSettings>();
+    let nav_mesh = app.world().resource::<NavMesh>().get();
+    let nav_mesh = nav_mesh.read().expect("Failed to get nav-mesh lock.");
+
+    f(&nav_mesh, nav_mesh_settings)
+}
+
+fn find_test_path(
+    app: &App,
+    start_pos: Vec3,
+    end_pos: Vec3,
+    position_search_radius: Option<f32>,
+) -> Result<Vec<Vec3>, FindPathError> {
+    with_nav_mesh(app, |nav_mesh, nav_mesh_settings| {
+        find_path(
+            nav_mesh,
+            nav_mesh_settings,
+            start_pos,
+            end_pos,
+            position_search_radius,
+            None,
+            None,
+        )
+    })
+}
+
+/// Returns every tile's debug representation, sorted by tile coordinate.
+fn get_tile_snapshots(app: &App) -> Vec<(UVec2, String)> {
+    with_nav_mesh(app, |nav_mesh, _| {
+        let mut tiles = nav_mesh
+            .get_tiles()
+            .iter()
+            .map(|(tile_coord, tile)| (*tile_coord, format!("{tile:?}")))
+            .collect::<Vec<_>>();
+        tiles.sort_by_key(|(tile_coord, _)| (tile_coord.x, tile_coord.y));
+
+        tiles
+    })
+}
+
+fn get_baked_nav_mesh(app: &App) -> BakedNavMesh {
+    with_nav_mesh(app, BakedNavMesh::from_nav_mesh)
+}
+
+/// Steps time by a fixed amount every update so agents don't depend on how fast the test runs.
+fn use_fixed_time_step(app: &mut App) {
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+        1.0 / 30.0,
+    )));
+}
+
+fn spawn_nav_agent(app: &mut App, position: Vec3, destination: Vec3) -> Entity {
+    app.world_mut()
+        .spawn((
+            TransformBundle::from_transform(Transform::from_translation(position)),
+            NavAgent::new(10.0).with_destination(destination),
+        ))
+        .id()
+}
+
+fn spawn_crowd_agent(app: &mut App, position: Vec3, target: Vec3) -> Entity {
+    app.world_mut()
+        .spawn((
+            TransformBundle::from_transform(Transform::from_translation(position)),
+            CrowdAgent::new(0.5, 3.0, 10.0).with_target(target),
+        ))
+        .id()
+}
+
+fn get_translation(app: &App, entity: Entity) -> Vec3 {
+    app.world().get::<Transform>(entity).unwrap().translation
+}
+
+/// Drains the pending ``E`` events, returning true if any of them matched ``predicate``.
+fn received_event<E: Event>(app: &mut App, predicate: impl FnMut(E) -> bool) -> bool {
+    app.world_mut()
+        .resource_mut::<Events<E>>()
+        .drain()
+        .any(predicate)
+}
+
 #[test]
 fn test_simple_navigation() {
-    let mut app = App::new();
+    let mut app = create_app(|_| {}, setup_world_system);
 
-    setup_app(&mut app);
+    wait_for_generation_to_finish(&mut app);
 
-    app.add_systems(Startup, setup_world_system);
+    if let Err(error) = find_test_path(&app, START_POS, END_POS, None) {
+        panic!("Pathfinding failed: {error:?}");
+    }
+}
+
+#[test]
+fn test_poly_ref_invalidated_by_regeneration() {
+    let mut app = create_app(|_| {}, setup_world_system);
 
     wait_for_generation_to_finish(&mut app);
 
-    let nav_mesh_settings = app.world().resource::<NavMeshSettings>();
+    let poly_ref = with_nav_mesh(&app, |nav_mesh, nav_mesh_settings| {
+        let (poly_ref, _) = nav_mesh
+            .find_closest_polygon_in_box(nav_mesh_settings, START_POS, 5.0, None)
+            .expect("No polygon found near position.");
+        assert!(nav_mesh.is_valid_poly_ref(poly_ref));
+
+        poly_ref
+    });
+
+    // Spawn a cube next to the position to dirty its tile.
+    app.world_mut()
+        .spawn(cube_bundle(Vec3::new(7.0, 0.8, 7.0), 0.5));
+
+    wait_for_generation_to_finish(&mut app);
+
+    assert!(!with_nav_mesh(&app, |nav_mesh, _| nav_mesh.is_valid_poly_ref(poly_ref)));
+}
+
+#[test]
+fn test_closed_door_blocks_path() {
+    let mut app = create_app(|_| {}, setup_world_system);
+
+    wait_for_generation_to_finish(&mut app);
+
+    // Closed door covering the end position.
+    let door = app
+        .world_mut()
+        .spawn((
+            TransformBundle::from_transform(Transform::from_translation(END_POS)),
+            NavMeshDoor::new(Vec3::new(6.0, 2.0, 6.0), false),
+        ))
+        .id();
+    // ``GlobalTransform`` is only propagated in ``PostUpdate``, the door is placed on the second update.
+    app.update();
+    app.update();
+
+    assert!(
+        matches!(
+            find_test_path(&app, START_POS, END_POS, None),
+            Err(FindPathError::PolygonPath(
+                FindPolygonPathError::NoValidEndPolygon
+            ))
+        ),
+        "Path should be blocked by the closed door."
+    );
+
+    // Known refs on the closed door are rejected too.
+    with_nav_mesh(&app, |nav_mesh, nav_mesh_settings| {
+        let unfiltered = Some(PolygonFilter {
+            include_flags: u16::MAX,
+            exclude_flags: 0,
+        });
+        let (start_ref, start_pos) = nav_mesh
+            .find_closest_polygon_in_box(nav_mesh_settings, START_POS, 5.0, None)
+            .expect("No start polygon.");
+        let (door_ref, door_pos) = nav_mesh
+            .find_closest_polygon_in_box(nav_mesh_settings, END_POS, 5.0, unfiltered)
+            .expect("No polygon under the door.");
+        assert!(matches!(
+            find_polygon_path_between_refs(
+                nav_mesh, start_ref, start_pos, door_ref, door_pos, None, None
+            ),
+            Err(FindPolygonPathError::NoValidEndPolygon)
+        ));
+        assert!(matches!(
+            find_polygon_path_between_refs(
+                nav_mesh, door_ref, door_pos, start_ref, start_pos, None, None
+            ),
+            Err(FindPolygonPathError::NoValidStartPolygon)
+        ));
+        assert!(find_polygon_path_between_refs(
+            nav_mesh, door_ref, door_pos, start_ref, start_pos, None, unfiltered
+        )
+        .is_ok());
+    });
+
+    app.world_mut()
+        .get_mut::<NavMeshDoor>(door)
+        .expect("Door was despawned.")
+        .open = true;
+    app.update();
+
+    if let Err(error) = find_test_path(&app, START_POS, END_POS, None) {
+        panic!("Pathfinding failed after opening the door: {error:?}");
+    }
+}
+
+#[test]
+fn test_obstacle_blocks_path() {
+    let mut app = create_app(
+        |nav_mesh_settings| nav_mesh_settings.cache_open_tiles = true,
+        setup_world_system,
+    );
+
+    wait_for_generation_to_finish(&mut app);
+
+    let obstacle = app.world_mut().spawn(obstacle_bundle(END_POS)).id();
+
+    wait_for_generation_to_finish(&mut app);
+
+    assert!(
+        find_test_path(&app, START_POS, END_POS, Some(1.0)).is_err(),
+        "Path should be blocked by the obstacle."
+    );
+
+    app.world_mut().despawn(obstacle);
+
+    wait_for_generation_to_finish(&mut app);
+
+    if let Err(error) = find_test_path(&app, START_POS, END_POS, Some(1.0)) {
+        panic!("Pathfinding failed after removing the obstacle: {error:?}");
+    }
+}
+
+#[test]
+fn test_static_affector_layer() {
+    let mut app = create_app(|_| {}, || {});
+
+    let plane = app
+        .world_mut()
+        .spawn((floor_bundle(Vec3::ZERO), NavMeshStaticAffector))
+        .id();
+
+    wait_for_generation_to_finish(&mut app);
+
+    // Dirty the tile with a non-static affector so it's rebuilt on top of the cached static layer.
+    app.world_mut()
+        .spawn(cube_bundle(Vec3::new(-5.0, 0.8, -5.0), 1.25));
+
+    wait_for_generation_to_finish(&mut app);
+
+    if let Err(error) = find_test_path(&app, START_POS, END_POS, Some(1.0)) {
+        panic!("Pathfinding failed with cached static layer: {error:?}");
+    }
+
+    // Removing the static affector must discard the cached layer.
+    app.world_mut().despawn(plane);
+
+    wait_for_generation_to_finish(&mut app);
+
+    assert!(
+        find_test_path(&app, START_POS, END_POS, Some(1.0)).is_err(),
+        "Path should not exist without the ground."
+    );
+}
+
+#[test]
+fn test_crowd_agents_avoid_each_other() {
+    let mut app = create_app(|_| {}, setup_world_system);
+    app.add_plugins(OxidizedNavigationCrowdPlugin::new(CrowdSettings::default()));
+
+    wait_for_generation_to_finish(&mut app);
+
+    use_fixed_time_step(&mut app);
+
+    // Two agents walking straight at each other.
+    let start_a = Vec3::new(8.0, 0.1, 0.0);
+    let start_b = Vec3::new(-8.0, 0.1, 0.1);
+    let agent_a = spawn_crowd_agent(&mut app, start_a, start_b);
+    let agent_b = spawn_crowd_agent(&mut app, start_b, start_a);
+
+    let mut min_distance = f32::INFINITY;
+    for _ in 0..300 {
+        app.update();
+
+        let position_a = get_translation(&app, agent_a);
+        let position_b = get_translation(&app, agent_b);
+        min_distance = min_distance.min(position_a.xz().distance(position_b.xz()));
+    }
+
+    assert!(
+        min_distance > 0.9,
+        "Agents came too close to each other: {min_distance}"
+    );
+
+    let position_a = get_translation(&app, agent_a);
+    let position_b = get_translation(&app, agent_b);
+    assert!(
+        position_a.xz().distance(start_b.xz()) < 0.5,
+        "Agent A didn't arrive: {position_a}"
+    );
+    assert!(
+        position_b.xz().distance(start_a.xz()) < 0.5,
+        "Agent B didn't arrive: {position_b}"
+    );
+}
+
+#[test]
+fn test_nav_agent_arrives() {
+    let mut app = create_app(|_| {}, setup_world_system);
+    app.add_plugins(OxidizedNavigationAgentPlugin);
+
+    wait_for_generation_to_finish(&mut app);
+
+    use_fixed_time_step(&mut app);
+
+    let destination = Vec3::new(-15.0, 0.1, -15.0);
+    let agent = spawn_nav_agent(&mut app, Vec3::new(5.0, 0.1, 5.0), destination);
+    // Unreachable destination far outside the nav-mesh.
+    let lost_agent = spawn_nav_agent(
+        &mut app,
+        Vec3::new(5.0, 0.1, 5.0),
+        Vec3::new(200.0, 50.0, 200.0),
+    );
+
+    let mut arrived = false;
+    let mut failed = false;
+    for _ in 0..150 {
+        app.update();
+
+        arrived |= received_event(&mut app, |event: NavAgentArrived| event.0 == agent);
+        failed |= received_event(&mut app, |event: NavAgentPathFailed| {
+            event.entity == lost_agent
+        });
+    }
+
+    assert!(arrived, "Agent never arrived.");
+    assert!(failed, "Agent with unreachable destination never failed.");
+
+    let position = get_translation(&app, agent);
+    assert!(position.xz().distance(destination.xz()) < 0.5);
+    assert_eq!(
+        app.world().get::<NavAgent>(agent).unwrap().status(),
+        NavAgentStatus::Arrived
+    );
+}
+
+#[test]
+fn test_nav_agent_arrives_at_off_mesh_destination() {
+    let mut app = create_app(|_| {}, setup_world_system);
+    app.add_plugins(OxidizedNavigationAgentPlugin);
+
+    wait_for_generation_to_finish(&mut app);
+
+    use_fixed_time_step(&mut app);
+
+    // Destination inside the cube, the agent can only get to its side.
+    let destination = Vec3::new(-5.0, 0.1, -5.0);
+    let agent = spawn_nav_agent(&mut app, Vec3::new(5.0, 0.1, -5.0), destination);
+
+    let mut arrived = false;
+    for _ in 0..100 {
+        app.update();
+
+        arrived |= received_event(&mut app, |event: NavAgentArrived| event.0 == agent);
+    }
+
+    assert!(arrived, "Agent never arrived.");
+    let position = get_translation(&app, agent);
+    assert!(
+        position.xz().distance(destination.xz()) < 2.0,
+        "Agent stopped too far from its destination: {position}"
+    );
+    assert_eq!(
+        app.world().get::<NavAgent>(agent).unwrap().status(),
+        NavAgentStatus::Arrived
+    );
+}
+
+fn setup_ledge_world_system(mut commands: Commands) {
+    // Ground
+    commands.spawn(affector_bundle(
+        Transform::from_xyz(12.5, -0.1, 12.5),
+        SharedShape::cuboid(12.0, 0.1, 12.0),
+    ));
+
+    // Two platforms too high to step onto with a small gap between them.
+    for x in [8.0, 15.0] {
+        commands.spawn(affector_bundle(
+            Transform::from_xyz(x, 0.5, 12.5),
+            SharedShape::cuboid(3.0, 0.5, 3.0),
+        ));
+    }
+}
+
+#[test]
+fn test_jump_links() {
+    let mut app = create_app(
+        |nav_mesh_settings| {
+            nav_mesh_settings.jump_links = Some(JumpLinkSettings {
+                max_drop_height: 20,
+                max_jump_distance: 8,
+                max_climb_height: 0,
+                jump_down_area: Area(2),
+                jump_across_area: Area(3),
+            })
+        },
+        setup_ledge_world_system,
+    );
+
+    wait_for_generation_to_finish(&mut app);
+
+    with_nav_mesh(&app, |nav_mesh, _| {
+        let mut links = nav_mesh
+            .get_tiles()
+            .values()
+            .flat_map(|tile| tile.off_mesh_links.iter());
+        assert!(links
+            .clone()
+            .any(|link| link.kind == OffMeshLinkKind::JumpDown && !link.bidirectional));
+        assert!(links.any(|link| link.kind == OffMeshLinkKind::JumpAcross && link.bidirectional));
+    });
+
+    let ground = Vec3::new(3.0, 0.0, 3.0);
+    let left_platform = Vec3::new(7.0, 1.0, 12.5);
+    let right_platform = Vec3::new(16.0, 1.0, 12.5);
+
+    // Unreachable destinations result in a partial path, so check where the path ends.
+    let reaches = |start_pos: Vec3, end_pos: Vec3| match find_test_path(
+        &app,
+        start_pos,
+        end_pos,
+        Some(1.0),
+    ) {
+        Ok(path) => path.last().unwrap().distance(end_pos) < 0.5,
+        Err(_) => false,
+    };
+    assert!(
+        reaches(left_platform, ground),
+        "Failed to drop down from the platform."
+    );
+    assert!(
+        !reaches(ground, left_platform),
+        "Drop down links should be one-way."
+    );
+    assert!(
+        reaches(left_platform, right_platform),
+        "Failed to jump across the gap."
+    );
+    assert!(
+        reaches(right_platform, left_platform),
+        "Failed to jump back across the gap."
+    );
+}
+
+#[test]
+fn test_area_volumes() {
+    let mut app = create_app(
+        |nav_mesh_settings| nav_mesh_settings.cache_open_tiles = true,
+        setup_world_system,
+    );
+
+    wait_for_generation_to_finish(&mut app);
+
+    let box_volume = app
+        .world_mut()
+        .spawn((
+            TransformBundle::from_transform(Transform::from_xyz(10.0, 0.0, 10.0)),
+            NavMeshAreaVolume {
+                shape: NavMeshVolumeShape::Box {
+                    half_extents: Vec3::new(3.0, 1.0, 3.0),
+                },
+                area: Some(Area(5)),
+            },
+        ))
+        .id();
+    app.world_mut().spawn((
+        TransformBundle::from_transform(Transform::from_xyz(-10.0, 0.0, 10.0)),
+        NavMeshAreaVolume {
+            shape: NavMeshVolumeShape::ConvexPrism {
+                points: vec![
+                    Vec2::new(-3.0, -3.0),
+                    Vec2::new(3.0, -3.0),
+                    Vec2::new(0.0, 3.0),
+                ],
+                min_height: -1.0,
+                max_height: 1.0,
+            },
+            area: Some(Area(6)),
+        },
+    ));
+
+    wait_for_generation_to_finish(&mut app);
+
+    let area_at = |app: &App, position: Vec3| {
+        with_nav_mesh(app, |nav_mesh, nav_mesh_settings| {
+            let (poly_ref, _) = nav_mesh
+                .find_polygon_at_point(nav_mesh_settings, position, 1.0, None)
+                .expect("No polygon at position.");
+            let (_, polygon) = nav_mesh.get_tile_and_polygon_by_ref(poly_ref).unwrap();
+
+            polygon.area
+        })
+    };
+
+    assert_eq!(area_at(&app, Vec3::new(10.0, 0.1, 10.0)), Area(5));
+    assert_eq!(area_at(&app, Vec3::new(-10.0, 0.1, 9.0)), Area(6));
+    assert_ne!(area_at(&app, Vec3::new(-10.0, 0.1, 15.0)), Area(6));
+
+    app.world_mut().despawn(box_volume);
+
+    wait_for_generation_to_finish(&mut app);
+
+    assert_ne!(area_at(&app, Vec3::new(10.0, 0.1, 10.0)), Area(5));
+}
+
+#[test]
+fn test_blocker_cuts_hole() {
+    let mut app = create_app(|_| {}, setup_world_system);
+
+    wait_for_generation_to_finish(&mut app);
+
+    let blocker = app
+        .world_mut()
+        .spawn((
+            TransformBundle::from_transform(Transform::from_translation(END_POS.with_y(0.0))),
+            NavMeshBlocker {
+                shape: NavMeshVolumeShape::Box {
+                    half_extents: Vec3::new(3.0, 2.0, 3.0),
+                },
+            },
+        ))
+        .id();
+
+    wait_for_generation_to_finish(&mut app);
+
+    assert!(
+        find_test_path(&app, START_POS, END_POS, Some(1.0)).is_err(),
+        "Path should be blocked by the blocker."
+    );
+
+    // The nav-mesh is pulled back from the blocker like from a wall.
+    let just_outside = Vec3::new(END_POS.x + 3.1, 0.1, END_POS.z);
+    assert!(with_nav_mesh(&app, |nav_mesh, nav_mesh_settings| {
+        nav_mesh
+            .find_polygon_at_point(nav_mesh_settings, just_outside, 1.0, None)
+            .is_none()
+    }));
+
+    app.world_mut().despawn(blocker);
+
+    wait_for_generation_to_finish(&mut app);
+
+    if let Err(error) = find_test_path(&app, START_POS, END_POS, Some(1.0)) {
+        panic!("Pathfinding failed after removing the blocker: {error:?}");
+    }
+}
+
+#[test]
+fn test_region_partitioning() {
+    for partitioning in [RegionPartitioning::Monotone, RegionPartitioning::Layers] {
+        let mut app = create_app(
+            |nav_mesh_settings| nav_mesh_settings.partitioning = partitioning,
+            setup_world_system,
+        );
+
+        wait_for_generation_to_finish(&mut app);
+
+        let path = find_test_path(&app, START_POS, END_POS, None)
+            .unwrap_or_else(|error| panic!("Pathfinding failed with {partitioning:?}: {error:?}"));
+
+        let last = *path.last().expect("Path is empty.");
+        assert!(
+            last.xz().distance(END_POS.xz()) < 0.5,
+            "Path didn't reach the goal with {partitioning:?}, ended at {last}."
+        );
+    }
+}
+
+#[test]
+fn test_tessellation() {
+    let center = Vec3::new(10.0, 0.1, 10.0);
+    let radius = 4.0;
+
+    for (tessellation, expect_walkable_inside) in [
+        (None, false),
+        (Some(Tessellation::Fixed(3)), true),
+        // Too few subdivisions are clamped instead of dropping the tower.
+        (Some(Tessellation::Fixed(0)), true),
+        (
+            Some(Tessellation::Adaptive {
+                segment_length: 1000.0,
+                min: 0,
+                max: 64,
+            }),
+            true,
+        ),
+        (
+            Some(Tessellation::Adaptive {
+                segment_length: 4.0,
+                min: 3,
+                max: 64,
+            }),
+            false,
+        ),
+    ] {
+        let mut app = create_app(
+            |_| {},
+            move |mut commands: Commands| {
+                let mut tower = commands.spawn(affector_bundle(
+                    Transform::from_xyz(center.x, 1.0, center.z),
+                    SharedShape::cylinder(1.0, radius),
+                ));
+                if let Some(tessellation) = tessellation {
+                    tower.insert(NavMeshTessellation(tessellation));
+                }
+                commands.spawn(floor_bundle(Vec3::ZERO));
+            },
+        );
+
+        wait_for_generation_to_finish(&mut app);
+
+        with_nav_mesh(&app, |nav_mesh, nav_mesh_settings| {
+            // A coarse outline leaves parts of the ground inside the tower's radius walkable.
+            let walkable_inside = (0..24).any(|i| {
+                let angle = i as f32 / 24.0 * std::f32::consts::TAU;
+                let position = center + Vec3::new(angle.cos(), 0.0, angle.sin()) * radius * 0.75;
+
+                nav_mesh
+                    .find_polygon_at_point(nav_mesh_settings, position, 0.5, None)
+                    .is_some_and(|(_, point)| point.xz().distance(position.xz()) < 0.01)
+            });
+            assert_eq!(
+                walkable_inside, expect_walkable_inside,
+                "With tessellation {tessellation:?}"
+            );
+            assert!(
+                nav_mesh
+                    .find_polygon_at_point(nav_mesh_settings, center, 0.5, None)
+                    .is_none(),
+                "Tower is missing with tessellation {tessellation:?}"
+            );
+        });
+    }
+}
+
+#[derive(Resource, Default)]
+struct GeneratedTiles(Vec<UVec2>);
+
+fn record_generated_tiles_system(
+    mut tile_generated: EventReader<TileGenerated>,
+    mut generated_tiles: ResMut<GeneratedTiles>,
+) {
+    generated_tiles
+        .0
+        .extend(tile_generated.read().map(|tile_generated| tile_generated.0));
+}
+
+fn record_generated_tiles(app: &mut App) {
+    app.init_resource::<GeneratedTiles>()
+        .add_systems(Update, record_generated_tiles_system);
+}
+
+#[test]
+fn test_tile_priority() {
+    let anchor_position = Vec3::new(-20.0, 0.0, -20.0);
+
+    for use_callback in [false, true] {
+        let mut app = create_app(
+            |nav_mesh_settings| {
+                nav_mesh_settings.max_tile_generation_tasks = NonZeroU16::new(1);
+            },
+            setup_world_system,
+        );
+        record_generated_tiles(&mut app);
+
+        let nav_mesh_settings = app.world().resource::<NavMeshSettings>().clone();
+        let expected_first_tile = if use_callback {
+            // Prefer the tiles furthest along X & Z.
+            app.insert_resource(NavMeshTilePriority(Box::new(|tile_coord, _| {
+                -((tile_coord.x + tile_coord.y) as f32)
+            })));
+
+            nav_mesh_settings.get_tile_containing_position(Vec2::splat(30.0))
+        } else {
+            app.world_mut().spawn((
+                TransformBundle::from_transform(Transform::from_translation(anchor_position)),
+                NavMeshPriorityAnchor,
+            ));
+
+            nav_mesh_settings.get_tile_containing_position(anchor_position.xz())
+        };
+
+        wait_for_generation_to_finish(&mut app);
+
+        let generated_tiles = &app.world().resource::<GeneratedTiles>().0;
+        assert_eq!(
+            generated_tiles.first(),
+            Some(&expected_first_tile),
+            "Generated in order {generated_tiles:?}"
+        );
+    }
+}
+
+fn spawn_streaming_anchor(app: &mut App, position: Vec3) -> Entity {
+    app.world_mut()
+        .spawn((
+            TransformBundle::from_transform(Transform::from_translation(position)),
+            NavMeshStreamingAnchor,
+        ))
+        .id()
+}
+
+fn use_streaming(nav_mesh_settings: &mut NavMeshSettings) {
+    nav_mesh_settings.streaming = Some(StreamingSettings {
+        load_radius: 10.0,
+        unload_radius: 20.0,
+    });
+}
+
+#[test]
+fn test_streaming() {
+    let mut app = create_app(use_streaming, setup_world_system);
+    let anchor = spawn_streaming_anchor(&mut app, Vec3::new(-20.0, 0.0, -20.0));
+
+    let nav_mesh_settings = app.world().resource::<NavMeshSettings>().clone();
+    let near_tile = nav_mesh_settings.get_tile_containing_position(Vec2::new(-20.0, -20.0));
+    let far_tile = nav_mesh_settings.get_tile_containing_position(Vec2::new(20.0, 20.0));
+
+    // Tiles are removed by detached tasks, keep updating until the nav-mesh catches up.
+    let wait_for_tiles = |app: &mut App, loaded: UVec2, unloaded: UVec2| {
+        let start = app.world().resource::<Time>().elapsed();
+        loop {
+            wait_for_generation_to_finish(app);
+
+            let caught_up = with_nav_mesh(app, |nav_mesh, _| {
+                let tiles = nav_mesh.get_tiles();
+                tiles.contains_key(&loaded) && !tiles.contains_key(&unloaded)
+            });
+            if caught_up {
+                break;
+            }
+
+            if app.world().resource::<Time>().elapsed() - start >= TIMEOUT_DURATION {
+                panic!(
+                    "Streaming timed out, expected {loaded} to be loaded & {unloaded} unloaded."
+                );
+            }
+            app.update();
+        }
+    };
+
+    wait_for_tiles(&mut app, near_tile, far_tile);
+
+    app.world_mut()
+        .entity_mut(anchor)
+        .insert(Transform::from_xyz(20.0, 0.0, 20.0));
+    wait_for_tiles(&mut app, far_tile, near_tile);
+
+    // Unloaded tiles are generated again when an anchor comes back.
+    app.world_mut()
+        .entity_mut(anchor)
+        .insert(Transform::from_xyz(-20.0, 0.0, -20.0));
+    wait_for_tiles(&mut app, near_tile, far_tile);
+}
+
+#[test]
+fn test_streaming_keeps_loaded_tiles_up_to_date() {
+    let mut app = create_app(use_streaming, setup_world_system);
+    record_generated_tiles(&mut app);
+    let anchor = spawn_streaming_anchor(&mut app, Vec3::new(-12.5, 0.0, -12.5));
+
+    let cube_tile = app
+        .world()
+        .resource::<NavMeshSettings>()
+        .get_tile_containing_position(Vec2::new(-5.0, -5.0));
+
+    // Waits until the cube's tile has been generated, returning false if it never is.
+    let wait_for_cube_tile = |app: &mut App| {
+        let start = app.world().resource::<Time>().elapsed();
+        while app.world().resource::<Time>().elapsed() - start < TIMEOUT_DURATION {
+            app.update();
+            if app
+                .world()
+                .resource::<GeneratedTiles>()
+                .0
+                .contains(&cube_tile)
+            {
+                return true;
+            }
+
+            std::thread::sleep(SLEEP_DURATION);
+        }
+
+        false
+    };
+
+    assert!(wait_for_cube_tile(&mut app));
+
+    // Further than the load radius but within the unload radius, the tile stays loaded.
+    app.world_mut()
+        .entity_mut(anchor)
+        .insert(Transform::from_xyz(12.5, 0.0, -12.5));
+    wait_for_generation_to_finish(&mut app);
+    app.world_mut().resource_mut::<GeneratedTiles>().0.clear();
+
+    move_cube(
+        &mut app,
+        Vec3::new(-5.0, 0.8, -5.0),
+        Vec3::new(-10.0, 0.8, -10.0),
+    );
+    assert!(
+        wait_for_cube_tile(&mut app),
+        "Loaded tile wasn't regenerated after its affectors changed."
+    );
+    assert!(with_nav_mesh(&app, |nav_mesh, _| nav_mesh
+        .get_tiles()
+        .contains_key(&cube_tile)));
+}
+
+#[test]
+fn test_affector_debounce() {
+    let debounce = Duration::from_millis(300);
+
+    let mut app = create_app(
+        |nav_mesh_settings| nav_mesh_settings.affector_debounce = Some(debounce),
+        setup_world_system,
+    );
+    record_generated_tiles(&mut app);
+
+    let cube = app
+        .world_mut()
+        .spawn(cube_bundle(Vec3::new(10.0, 0.8, 10.0), 1.0))
+        .id();
+
+    let nav_mesh_settings = app.world().resource::<NavMeshSettings>().clone();
+    let cube_tile = nav_mesh_settings.get_tile_containing_position(Vec2::new(10.0, 10.0));
+
+    let update_for = |app: &mut App, duration: Duration, mut update: Box<dyn FnMut(&mut App)>| {
+        let start = std::time::Instant::now();
+        while start.elapsed() < duration {
+            update(app);
+            app.update();
+            std::thread::sleep(SLEEP_DURATION);
+        }
+    };
+    let wait_for_tile = |app: &mut App, tile_coord: UVec2| {
+        let start = std::time::Instant::now();
+        while !app
+            .world()
+            .resource::<GeneratedTiles>()
+            .0
+            .contains(&tile_coord)
+        {
+            assert!(start.elapsed() < TIMEOUT_DURATION, "Generation timed out.");
+
+            app.update();
+            std::thread::sleep(SLEEP_DURATION);
+        }
+        wait_for_generation_to_finish(app);
+    };
+
+    // Nothing is built until the affectors have been still for the debounce duration.
+    update_for(&mut app, debounce / 2, Box::new(|_| {}));
+    assert!(app.world().resource::<GeneratedTiles>().0.is_empty());
+
+    wait_for_tile(&mut app, cube_tile);
+    app.world_mut().resource_mut::<GeneratedTiles>().0.clear();
+
+    // Moving the cube every frame holds back its tile.
+    let mut offset = 0.0;
+    update_for(
+        &mut app,
+        debounce * 2,
+        Box::new(move |app| {
+            offset += 0.01;
+            app.world_mut()
+                .entity_mut(cube)
+                .insert(Transform::from_xyz(10.0 + offset, 0.8, 10.0));
+        }),
+    );
+    assert!(
+        !app.world()
+            .resource::<GeneratedTiles>()
+            .0
+            .contains(&cube_tile),
+        "Tile was rebuilt while its affector was moving."
+    );
+
+    // Once the cube stops the tile is rebuilt once.
+    wait_for_tile(&mut app, cube_tile);
+    let rebuilds = app
+        .world()
+        .resource::<GeneratedTiles>()
+        .0
+        .iter()
+        .filter(|tile_coord| **tile_coord == cube_tile)
+        .count();
+    assert_eq!(rebuilds, 1);
+}
+
+#[test]
+fn test_deterministic_tiles() {
+    let mut tiles = Vec::new();
+    for reverse_order in [false, true] {
+        let mut app = create_app(
+            |nav_mesh_settings| {
+                nav_mesh_settings.deterministic = true;
+                nav_mesh_settings.max_tile_generation_tasks = NonZeroU16::new(1);
+            },
+            setup_world_system,
+        );
+        // Generate the tiles in opposite orders so neighbours are linked in different orders.
+        app.insert_resource(NavMeshTilePriority(Box::new(move |tile_coord, _| {
+            let priority = (tile_coord.x * 1000 + tile_coord.y) as f32;
+            if reverse_order {
+                -priority
+            } else {
+                priority
+            }
+        })));
+        if reverse_order {
+            // Freed entities are reused, giving the world's entities a different order by Entity.
+            for entity in (0..4)
+                .map(|_| app.world_mut().spawn_empty().id())
+                .collect::<Vec<_>>()
+            {
+                app.world_mut().despawn(entity);
+            }
+        }
+
+        wait_for_generation_to_finish(&mut app);
+
+        tiles.push(get_tile_snapshots(&app));
+    }
+
+    assert!(!tiles[0].is_empty());
+    assert!(tiles[0] == tiles[1], "Tiles differ between runs.");
+}
+
+#[derive(Resource, Default)]
+struct OutOfWorldBoundsEntities(Vec<Entity>);
+
+fn record_out_of_world_bounds_system(
+    mut out_of_world_bounds: EventReader<OutOfWorldBounds>,
+    mut entities: ResMut<OutOfWorldBoundsEntities>,
+) {
+    entities
+        .0
+        .extend(out_of_world_bounds.read().map(|event| event.entity));
+}
+
+#[test]
+fn test_world_bounds() {
+    let world_bounds = Rect::new(1000.0, 2000.0, 1100.0, 2100.0);
+
+    let mut app = create_app(
+        |nav_mesh_settings| nav_mesh_settings.world_bounds = world_bounds,
+        || {},
+    );
+    app.init_resource::<OutOfWorldBoundsEntities>()
+        .add_systems(Update, record_out_of_world_bounds_system);
+
+    let inside = app
+        .world_mut()
+        .spawn(floor_bundle(Vec3::new(1050.0, 0.0, 2050.0)))
+        .id();
+    // Plane around the world origin, entirely outside the bounds.
+    let outside = app.world_mut().spawn(floor_bundle(Vec3::ZERO)).id();
+
+    wait_for_generation_to_finish(&mut app);
+
+    let reported = &app.world().resource::<OutOfWorldBoundsEntities>().0;
+    assert!(reported.contains(&outside));
+    assert!(!reported.contains(&inside));
+
+    with_nav_mesh(&app, |nav_mesh, nav_mesh_settings| {
+        assert!(!nav_mesh.get_tiles().is_empty());
+        for tile in nav_mesh.get_tiles().values() {
+            for vertex in tile.vertices.iter() {
+                assert!(
+                    world_bounds.contains(vertex.xz()),
+                    "Vertex {vertex} is outside the world bounds."
+                );
+            }
+        }
+
+        // Queries don't clamp positions into the edge tiles.
+        assert!(nav_mesh
+            .find_polygon_at_point(nav_mesh_settings, Vec3::new(1030.0, 0.2, 2030.0), 1.0, None)
+            .is_some());
+        assert!(nav_mesh
+            .find_polygon_at_point(nav_mesh_settings, Vec3::new(10.0, 0.2, 10.0), 1.0, None)
+            .is_none());
+        assert!(nav_mesh
+            .find_closest_polygon_in_box(nav_mesh_settings, Vec3::new(10.0, 0.2, 10.0), 5.0, None)
+            .is_none());
+    });
+
+    let path = find_test_path(
+        &app,
+        Vec3::new(1030.0, 0.2, 2030.0),
+        Vec3::new(1070.0, 0.2, 2070.0),
+        None,
+    );
+    assert!(path.is_ok(), "Failed to find path within the world bounds.");
+
+    let path = find_test_path(
+        &app,
+        Vec3::new(10.0, 0.2, 10.0),
+        Vec3::new(-10.0, 0.2, -10.0),
+        None,
+    );
+    assert!(
+        matches!(
+            path,
+            Err(FindPathError::PolygonPath(
+                FindPolygonPathError::StartOutOfWorldBounds
+            ))
+        ),
+        "Found a path outside the world bounds."
+    );
+
+    let path = find_test_path(
+        &app,
+        Vec3::new(1030.0, 0.2, 2030.0),
+        Vec3::new(1030.0, 0.2, 1990.0),
+        None,
+    );
+    assert!(matches!(
+        path,
+        Err(FindPathError::PolygonPath(
+            FindPolygonPathError::EndOutOfWorldBounds
+        ))
+    ));
+}
+
+#[test]
+fn test_tall_world() {
+    // Well above world_bottom_bound + cell_height * u16::MAX.
+    let floor_height = 8000.0;
+
+    let mut app = create_app(
+        |_| {},
+        move |mut commands: Commands| {
+            commands.spawn(floor_bundle(Vec3::Y * floor_height));
+        },
+    );
+
+    wait_for_generation_to_finish(&mut app);
+
+    let position = with_nav_mesh(&app, |nav_mesh, nav_mesh_settings| {
+        nav_mesh.find_polygon_at_point(
+            nav_mesh_settings,
+            Vec3::new(5.0, floor_height + 0.1, 5.0),
+            1.0,
+            None,
+        )
+    });
+    let Some((_, position)) = position else {
+        panic!("No polygon found on the floor.");
+    };
+    assert!(
+        (position.y - floor_height).abs() < 0.5,
+        "Floor generated at {}",
+        position.y
+    );
+
+    let path = find_test_path(
+        &app,
+        Vec3::new(-10.0, floor_height + 0.1, -10.0),
+        Vec3::new(10.0, floor_height + 0.1, 10.0),
+        None,
+    );
+    assert!(path.is_ok(), "Failed to find path on the floor.");
+}
+
+fn setup_terrain_world_system(mut commands: Commands) {
+    let heights = parry3d_016::na::DMatrix::from_fn(129, 129, |row, column| {
+        ((row as f32 * 0.2).sin() + (column as f32 * 0.15).cos()) * 0.5
+    });
+
+    commands.spawn(affector_bundle(
+        Transform::IDENTITY,
+        SharedShape::heightfield(heights, parry3d_016::na::Vector3::new(50.0, 1.0, 50.0)),
+    ));
+}
+
+#[test]
+fn test_parallel_rasterization() {
+    let mut tiles = Vec::new();
+    for max_rasterization_tasks in [NonZeroU16::new(1), NonZeroU16::new(4)] {
+        let mut app = create_app(
+            |nav_mesh_settings| {
+                nav_mesh_settings.deterministic = true;
+                nav_mesh_settings.max_rasterization_tasks = max_rasterization_tasks;
+            },
+            setup_terrain_world_system,
+        );
+
+        wait_for_generation_to_finish(&mut app);
+
+        tiles.push(get_tile_snapshots(&app));
+    }
+
+    assert!(!tiles[0].is_empty());
+    assert!(
+        tiles[0] == tiles[1],
+        "Parallel rasterization produced different tiles."
+    );
+}
+
+#[test]
+fn test_bake_nav_mesh() {
+    let mut app = create_bake_app(|_| {});
+
+    let mut progress = Vec::new();
+    let generated_tiles = bake_nav_mesh(app.world_mut(), |bake_progress| {
+        progress.push(bake_progress)
+    });
+
+    assert!(generated_tiles > 0);
+    assert!(app.world().resource::<ActiveGenerationTasks>().is_empty());
+    let last_progress = progress.last().expect("No progress was reported.");
+    assert_eq!(last_progress.generated_tiles, generated_tiles);
+    assert_eq!(last_progress.remaining_tiles, 0);
+    assert!(progress
+        .windows(2)
+        .all(|window| window[0].generated_tiles < window[1].generated_tiles));
+
+    assert!(
+        find_test_path(&app, START_POS, END_POS, None).is_ok(),
+        "Failed to find path after baking."
+    );
+
+    // The bake's events are read on the next update.
+    app.update();
+    assert_eq!(
+        app.world().resource::<GeneratedTiles>().0.len(),
+        generated_tiles
+    );
+
+    // Nothing is left to generate once the bake returns.
+    app.world_mut().resource_mut::<GeneratedTiles>().0.clear();
+    for _ in 0..10 {
+        app.update();
+        std::thread::sleep(SLEEP_DURATION);
+    }
+    assert!(app.world().resource::<ActiveGenerationTasks>().is_empty());
+    assert!(
+        app.world().resource::<GeneratedTiles>().0.is_empty(),
+        "Tiles were generated again after baking."
+    );
+}
+
+#[test]
+fn test_export_nav_mesh() {
+    let mut app = create_bake_app(|_| {});
+    bake_nav_mesh(app.world_mut(), |_| {});
+
     let nav_mesh = app.world().resource::<NavMesh>().get();
     let nav_mesh = nav_mesh.read().expect("Failed to get nav-mesh lock.");
+    let tiles = || {
+        nav_mesh
+            .get_tiles()
+            .iter()
+            .map(|(tile_coord, tile)| (*tile_coord, tile))
+    };
+    let exported_tiles = nav_mesh
+        .get_tiles()
+        .values()
+        .filter(|tile| !tile.polygons.is_empty())
+        .count();
+    let polygons: usize = nav_mesh
+        .get_tiles()
+        .values()
+        .map(|tile| tile.polygons.len())
+        .sum();
+    assert!(polygons > 0);
 
-    let start_pos = Vec3::new(5.0, 1.0, 5.0);
-    let end_pos = Vec3::new(-15.0, 1.0, -15.0);
+    let mut obj = Vec::new();
+    write_obj(tiles(), &mut obj).expect("Failed to export OBJ.");
+    let obj = String::from_utf8(obj).expect("OBJ isn't valid UTF-8.");
 
-    // Run pathfinding to get a polygon path.
-    let path = find_path(&nav_mesh, nav_mesh_settings, start_pos, end_pos, None, None);
+    let vertices: Vec<Vec3> = obj
+        .lines()
+        .filter_map(|line| line.strip_prefix("v "))
+        .map(|line| {
+            let coordinates: Vec<f32> = line
+                .split(' ')
+                .map(|value| value.parse().unwrap())
+                .collect();
+            Vec3::from_slice(&coordinates)
+        })
+        .collect();
+    let faces: Vec<[usize; 3]> = obj
+        .lines()
+        .filter_map(|line| line.strip_prefix("f "))
+        .map(|line| {
+            let indices: Vec<usize> = line
+                .split(' ')
+                .map(|value| value.parse().unwrap())
+                .collect();
+            [indices[0] - 1, indices[1] - 1, indices[2] - 1]
+        })
+        .collect();
 
-    if let Err(error) = path {
-        panic!("Pathfinding failed: {error:?}");
+    assert_eq!(
+        obj.lines()
+            .filter(|line| line.starts_with("o tile_"))
+            .count(),
+        exported_tiles
+    );
+    assert!(obj.lines().any(|line| line == "usemtl area_0"));
+    assert_eq!(faces.len(), polygons);
+    // The floor should face up when viewed in other tools.
+    assert!(faces.iter().all(|[a, b, c]| {
+        let normal = (vertices[*b] - vertices[*a]).cross(vertices[*c] - vertices[*a]);
+        normal.y >= 0.0
+    }));
+
+    let mut glb = Vec::new();
+    write_glb(tiles(), &mut glb).expect("Failed to export glTF.");
+
+    assert_eq!(&glb[0..4], b"glTF");
+    assert_eq!(
+        u32::from_le_bytes(glb[8..12].try_into().unwrap()) as usize,
+        glb.len()
+    );
+    let json_length = u32::from_le_bytes(glb[12..16].try_into().unwrap()) as usize;
+    assert_eq!(&glb[16..20], b"JSON");
+    let json = std::str::from_utf8(&glb[20..20 + json_length]).expect("JSON isn't valid UTF-8.");
+    assert_eq!(json.matches(r#""name":"tile_"#).count(), exported_tiles);
+    assert!(json.contains(r#""name":"area_0""#));
+    assert_eq!(&glb[20 + json_length + 4..20 + json_length + 8], b"BIN\0");
+
+    // An empty nav-mesh exports a valid file without empty arrays or a BIN chunk.
+    let mut glb = Vec::new();
+    write_glb(std::iter::empty(), &mut glb).expect("Failed to export empty glTF.");
+
+    let json_length = u32::from_le_bytes(glb[12..16].try_into().unwrap()) as usize;
+    assert_eq!(glb.len(), 20 + json_length);
+    let json = std::str::from_utf8(&glb[20..]).expect("JSON isn't valid UTF-8.");
+    assert!(!json.contains("[]"), "Empty array in {json}");
+    assert!(json.contains(r#""scenes":[{}]"#));
+}
+
+#[test]
+fn test_load_baked_nav_mesh() {
+    let mut app = create_bake_app(|_| {});
+    bake_nav_mesh(app.world_mut(), |_| {});
+    let baked_nav_mesh = get_baked_nav_mesh(&app);
+    assert!(!baked_nav_mesh.tiles.is_empty());
+    assert!(baked_nav_mesh
+        .tiles
+        .iter()
+        .all(|baked_tile| baked_tile.tile.input_hash.is_some()));
+
+    // Load the baked nav-mesh in a new app with the same world.
+    let mut app = create_bake_app(|_| {});
+
+    let mut other_settings = baked_nav_mesh.clone();
+    other_settings.settings.cell_width *= 2.0;
+    assert!(!load_baked_nav_mesh(app.world_mut(), other_settings));
+    // Changes the order of links & modifiers.
+    let mut other_settings = baked_nav_mesh.clone();
+    other_settings.settings.deterministic = !other_settings.settings.deterministic;
+    assert!(!load_baked_nav_mesh(app.world_mut(), other_settings));
+
+    assert!(load_baked_nav_mesh(app.world_mut(), baked_nav_mesh.clone()));
+    bake_nav_mesh(app.world_mut(), |_| {});
+    app.update();
+
+    // Only the loaded tiles are reported, none of them are generated again.
+    let mut generated_tiles =
+        std::mem::take(&mut app.world_mut().resource_mut::<GeneratedTiles>().0);
+    generated_tiles.sort_by_key(|tile_coord| (tile_coord.y, tile_coord.x));
+    let baked_tiles: Vec<UVec2> = baked_nav_mesh
+        .tiles
+        .iter()
+        .map(|baked_tile| baked_tile.tile_coord)
+        .collect();
+    assert_eq!(generated_tiles, baked_tiles);
+    assert_eq!(
+        format!("{:?}", get_baked_nav_mesh(&app).tiles),
+        format!("{:?}", baked_nav_mesh.tiles)
+    );
+
+    // Tiles that changed since baking are generated.
+    move_cube(
+        &mut app,
+        Vec3::new(-5.0, 0.8, -5.0),
+        Vec3::new(-6.0, 0.8, -6.0),
+    );
+    app.update();
+    bake_nav_mesh(app.world_mut(), |_| {});
+    app.update();
+
+    let cube_tile = app
+        .world()
+        .resource::<NavMeshSettings>()
+        .get_tile_containing_position(Vec2::new(-6.0, -6.0));
+    assert_eq!(app.world().resource::<GeneratedTiles>().0, vec![cube_tile]);
+
+    // A tile that differed when loading gets the baked tile back once its geometry matches again.
+    let mut app = create_bake_app(|_| {});
+
+    move_cube(
+        &mut app,
+        Vec3::new(-5.0, 0.8, -5.0),
+        Vec3::new(-6.0, 0.8, -6.0),
+    );
+    app.update();
+
+    assert!(load_baked_nav_mesh(app.world_mut(), baked_nav_mesh.clone()));
+    bake_nav_mesh(app.world_mut(), |_| {});
+    app.update();
+    assert_ne!(
+        format!("{:?}", get_baked_nav_mesh(&app).tiles),
+        format!("{:?}", baked_nav_mesh.tiles)
+    );
+
+    move_cube(
+        &mut app,
+        Vec3::new(-6.0, 0.8, -6.0),
+        Vec3::new(-5.0, 0.8, -5.0),
+    );
+    app.world_mut().resource_mut::<GeneratedTiles>().0.clear();
+    app.update();
+    bake_nav_mesh(app.world_mut(), |_| {});
+    app.update();
+
+    assert_eq!(app.world().resource::<GeneratedTiles>().0, vec![cube_tile]);
+    assert_eq!(
+        format!("{:?}", get_baked_nav_mesh(&app).tiles),
+        format!("{:?}", baked_nav_mesh.tiles)
+    );
+
+    // A baked tile whose geometry keeps changing is discarded & the tile is generated as usual from then on.
+    let mut marked_nav_mesh = baked_nav_mesh.clone();
+    for baked_tile in marked_nav_mesh.tiles.iter_mut() {
+        if baked_tile.tile_coord == cube_tile {
+            for polygon in baked_tile.tile.polygons.iter_mut() {
+                polygon.flags = 0b1010;
+            }
+        }
+    }
+
+    let mut app = create_bake_app(|_| {});
+
+    move_cube(
+        &mut app,
+        Vec3::new(-5.0, 0.8, -5.0),
+        Vec3::new(-6.0, 0.8, -6.0),
+    );
+    app.update();
+    assert!(load_baked_nav_mesh(app.world_mut(), marked_nav_mesh));
+    bake_nav_mesh(app.world_mut(), |_| {});
+
+    move_cube(
+        &mut app,
+        Vec3::new(-6.0, 0.8, -6.0),
+        Vec3::new(-7.0, 0.8, -7.0),
+    );
+    app.update();
+    bake_nav_mesh(app.world_mut(), |_| {});
+
+    move_cube(
+        &mut app,
+        Vec3::new(-7.0, 0.8, -7.0),
+        Vec3::new(-5.0, 0.8, -5.0),
+    );
+    app.update();
+    bake_nav_mesh(app.world_mut(), |_| {});
+
+    assert!(with_nav_mesh(&app, |nav_mesh, _| {
+        nav_mesh.get_tiles()[&cube_tile]
+            .polygons
+            .iter()
+            .all(|polygon| polygon.flags != 0b1010)
+    }));
+}
+
+#[test]
+fn test_obstacle_on_baked_tile() {
+    let cache_open_tiles = |nav_mesh_settings: &mut NavMeshSettings| {
+        nav_mesh_settings.cache_open_tiles = true;
+    };
+
+    let mut app = create_bake_app(cache_open_tiles);
+    bake_nav_mesh(app.world_mut(), |_| {});
+    let baked_nav_mesh = get_baked_nav_mesh(&app);
+
+    let mut app = create_bake_app(cache_open_tiles);
+    assert!(load_baked_nav_mesh(app.world_mut(), baked_nav_mesh));
+    bake_nav_mesh(app.world_mut(), |_| {});
+
+    // The baked tiles never filled the open tile cache, the obstacle needs a full rebuild.
+    app.world_mut().spawn(obstacle_bundle(END_POS));
+
+    let start = app.world().resource::<Time>().elapsed();
+    loop {
+        app.update();
+
+        if find_test_path(&app, START_POS, END_POS, Some(1.0)).is_err() {
+            break;
+        }
+
+        assert!(
+            app.world().resource::<Time>().elapsed() - start < TIMEOUT_DURATION,
+            "Obstacle was never applied to the baked tile."
+        );
+        std::thread::sleep(SLEEP_DURATION);
+    }
+
+    // Nothing is left waiting on the cache.
+    bake_nav_mesh(app.world_mut(), |_| {});
+}
+
+fn move_cube(app: &mut App, from: Vec3, to: Vec3) {
+    let mut cube_query = app
+        .world_mut()
+        .query_filtered::<&mut Transform, With<MyParryCollider>>();
+    for mut transform in cube_query.iter_mut(app.world_mut()) {
+        if transform.translation == from {
+            transform.translation = to;
+        }
     }
 }
@@ -3,8 +3,8 @@ use std::{num::NonZeroU16, time::Duration};
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::{Collider, NoUserData, RapierPhysicsPlugin};
 use oxidized_navigation::{
-    query::find_path, ActiveGenerationTasks, NavMesh, NavMeshAffector, NavMeshSettings,
-    OxidizedNavigationPlugin,
+    conversion::Tessellation, query::find_path, ActiveGenerationTasks, NavMesh, NavMeshAffector,
+    NavMeshSettings, OxidizedNavigationPlugin, RegionPartitioning, SpanFilters,
 };
 
 const TIMEOUT_DURATION: Duration = Duration::new(15, 0);
@@ -69,7 +69,7 @@ fn setup_app(app: &mut App) {
             cell_width: 0.25,
             cell_height: 0.1,
             tile_width: 100,
-            world_half_extents: 250.0,
+            world_bounds: Rect::from_center_half_size(Vec2::ZERO, Vec2::splat(250.0)),
             world_bottom_bound: -100.0,
             max_traversable_slope_radians: (40.0_f32 - 0.1).to_radians(),
             walkable_height: 20,
@@ -80,6 +80,16 @@ fn setup_app(app: &mut App) {
             max_contour_simplification_error: 1.1,
             max_edge_length: 80,
             max_tile_generation_tasks: NonZeroU16::new(8), // Github Actions are limited to 7 GB.
+            max_rasterization_tasks: None,
+            cache_open_tiles: false,
+            jump_links: None,
+            partitioning: RegionPartitioning::Watershed,
+            span_filters: SpanFilters::default(),
+            median_area_filter: false,
+            tessellation: Tessellation::default(),
+            streaming: None,
+            affector_debounce: None,
+            deterministic: false,
         }),
         RapierPhysicsPlugin::<NoUserData>::default(),
     ));
@@ -118,7 +128,15 @@ fn test_simple_navigation() {
     let end_pos = Vec3::new(-15.0, 1.0, -15.0);
 
     // Run pathfinding to get a polygon path.
-    let path = find_path(&nav_mesh, nav_mesh_settings, start_pos, end_pos, None, None);
+    let path = find_path(
+        &nav_mesh,
+        nav_mesh_settings,
+        start_pos,
+        end_pos,
+        None,
+        None,
+        None,
+    );
 
     if let Err(error) = path {
         panic!("Pathfinding failed: {error:?}");
@@ -143,7 +161,15 @@ fn test_heightfield_navigation() {
     let end_pos = Vec3::new(-15.0, 1.0, -15.0);
 
     // Run pathfinding to get a polygon path.
-    let path = find_path(&nav_mesh, nav_mesh_settings, start_pos, end_pos, None, None);
+    let path = find_path(
+        &nav_mesh,
+        nav_mesh_settings,
+        start_pos,
+        end_pos,
+        None,
+        None,
+        None,
+    );
 
     if let Err(error) = path {
         panic!("Pathfinding failed: {error:?}");